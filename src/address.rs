@@ -0,0 +1,445 @@
+use crate::country::{Country, CountryError};
+use crate::{inputs, UserInput};
+use std::collections;
+
+/// The address rules for a country.
+#[derive(Debug)]
+pub struct AddressInfo {
+    code: &'static str,
+    city_required: bool,
+    region_required: bool,
+    // empty if the region is free text
+    regions: &'static [(&'static str, &'static str)],
+    // empty if the country has no postcodes
+    postcodes: &'static [&'static str],
+    postcode_required: bool,
+    postcode_example: &'static str,
+}
+
+macro_rules! country {
+    ($code:expr, $city:expr, $region:expr, $regions:expr, $postcodes:expr, $postcode:expr, $example:expr) => {
+        AddressInfo {
+            code: $code,
+            city_required: $city,
+            region_required: $region,
+            regions: $regions,
+            postcodes: $postcodes,
+            postcode_required: $postcode,
+            postcode_example: $example,
+        }
+    };
+}
+
+const AU_STATES: &[(&str, &str)] = &[
+    ("ACT", "Australian Capital Territory"),
+    ("NSW", "New South Wales"),
+    ("NT", "Northern Territory"),
+    ("QLD", "Queensland"),
+    ("SA", "South Australia"),
+    ("TAS", "Tasmania"),
+    ("VIC", "Victoria"),
+    ("WA", "Western Australia"),
+];
+
+const CA_PROVINCES: &[(&str, &str)] = &[
+    ("AB", "Alberta"),
+    ("BC", "British Columbia"),
+    ("MB", "Manitoba"),
+    ("NB", "New Brunswick"),
+    ("NL", "Newfoundland and Labrador"),
+    ("NS", "Nova Scotia"),
+    ("NT", "Northwest Territories"),
+    ("NU", "Nunavut"),
+    ("ON", "Ontario"),
+    ("PE", "Prince Edward Island"),
+    ("QC", "Quebec"),
+    ("SK", "Saskatchewan"),
+    ("YT", "Yukon"),
+];
+
+const US_STATES: &[(&str, &str)] = &[
+    ("AK", "Alaska"),
+    ("AL", "Alabama"),
+    ("AR", "Arkansas"),
+    ("AZ", "Arizona"),
+    ("CA", "California"),
+    ("CO", "Colorado"),
+    ("CT", "Connecticut"),
+    ("DC", "District of Columbia"),
+    ("DE", "Delaware"),
+    ("FL", "Florida"),
+    ("GA", "Georgia"),
+    ("HI", "Hawaii"),
+    ("IA", "Iowa"),
+    ("ID", "Idaho"),
+    ("IL", "Illinois"),
+    ("IN", "Indiana"),
+    ("KS", "Kansas"),
+    ("KY", "Kentucky"),
+    ("LA", "Louisiana"),
+    ("MA", "Massachusetts"),
+    ("MD", "Maryland"),
+    ("ME", "Maine"),
+    ("MI", "Michigan"),
+    ("MN", "Minnesota"),
+    ("MO", "Missouri"),
+    ("MS", "Mississippi"),
+    ("MT", "Montana"),
+    ("NC", "North Carolina"),
+    ("ND", "North Dakota"),
+    ("NE", "Nebraska"),
+    ("NH", "New Hampshire"),
+    ("NJ", "New Jersey"),
+    ("NM", "New Mexico"),
+    ("NV", "Nevada"),
+    ("NY", "New York"),
+    ("OH", "Ohio"),
+    ("OK", "Oklahoma"),
+    ("OR", "Oregon"),
+    ("PA", "Pennsylvania"),
+    ("RI", "Rhode Island"),
+    ("SC", "South Carolina"),
+    ("SD", "South Dakota"),
+    ("TN", "Tennessee"),
+    ("TX", "Texas"),
+    ("UT", "Utah"),
+    ("VA", "Virginia"),
+    ("VT", "Vermont"),
+    ("WA", "Washington"),
+    ("WI", "Wisconsin"),
+    ("WV", "West Virginia"),
+    ("WY", "Wyoming"),
+];
+
+// sorted by code. Postcode patterns use `9` for a digit, `A` for a letter and `X` for either; a
+// space in the pattern is optional in the input.
+const ADDRESS_INFO: &[AddressInfo] = &[
+    country!("AU", true, true, AU_STATES, &["9999"], true, "2000"),
+    country!("BR", true, true, &[], &["99999-999"], true, "01310-100"),
+    country!(
+        "CA",
+        true,
+        true,
+        CA_PROVINCES,
+        &["A9A 9A9"],
+        true,
+        "K1A 0B1"
+    ),
+    country!("CN", true, true, &[], &["999999"], true, "100000"),
+    country!("DE", true, false, &[], &["99999"], true, "10115"),
+    country!("ES", true, false, &[], &["99999"], true, "28001"),
+    country!("FR", true, false, &[], &["99999"], true, "75001"),
+    country!(
+        "GB",
+        true,
+        false,
+        &[],
+        &["A9 9AA", "A99 9AA", "A9A 9AA", "AA9 9AA", "AA99 9AA", "AA9A 9AA"],
+        true,
+        "SW1A 1AA"
+    ),
+    country!("IE", true, false, &[], &["A99 XXXX"], false, "D02 X285"),
+    country!("IN", true, true, &[], &["999999"], true, "110001"),
+    country!("IT", true, false, &[], &["99999"], true, "00118"),
+    country!("JP", true, true, &[], &["999-9999"], true, "100-0001"),
+    country!("MX", true, true, &[], &["99999"], true, "06000"),
+    country!("NL", true, false, &[], &["9999 AA"], true, "1012 AB"),
+    country!("NZ", true, false, &[], &["9999"], true, "6011"),
+    country!("SG", false, false, &[], &["999999"], true, "018956"),
+    country!(
+        "US",
+        true,
+        true,
+        US_STATES,
+        &["99999", "99999-9999"],
+        true,
+        "20500"
+    ),
+    country!("ZA", true, false, &[], &["9999"], true, "0001"),
+];
+
+impl AddressInfo {
+    /// `None` if there are no address rules for `country`.
+    pub fn of(country: Country) -> Option<&'static AddressInfo> {
+        ADDRESS_INFO.iter().find(|info| info.code == country.code())
+    }
+    /// The countries with address rules, eg for the options of an `Address`.
+    pub fn countries() -> collections::BTreeSet<Country> {
+        Country::all()
+            .into_iter()
+            .filter(|country| AddressInfo::of(*country).is_some())
+            .collect()
+    }
+    pub fn city_required(&self) -> bool {
+        self.city_required
+    }
+    pub fn region_required(&self) -> bool {
+        self.region_required
+    }
+    /// The `(code, name)` of each region to choose from, or empty if the region is free text.
+    pub fn regions(&self) -> &'static [(&'static str, &'static str)] {
+        self.regions
+    }
+    pub fn has_postcodes(&self) -> bool {
+        !self.postcodes.is_empty()
+    }
+    pub fn postcode_required(&self) -> bool {
+        self.has_postcodes() && self.postcode_required
+    }
+    pub fn postcode_example(&self) -> &'static str {
+        self.postcode_example
+    }
+    /// The postcode in its standard form (eg `SW1A 1AA` for `sw1a1aa`), if it is valid.
+    pub fn normalise_postcode(&self, postcode: &str) -> Option<String> {
+        let postcode = postcode.trim().to_uppercase();
+        self.postcodes
+            .iter()
+            .find_map(|pattern| match_postcode(pattern, &postcode))
+    }
+    /// The region's code, if it is one of the options (by code or name).
+    pub fn find_region(&self, region: &str) -> Option<&'static str> {
+        let region = region.trim();
+        self.regions()
+            .iter()
+            .find(|(code, name)| {
+                code.eq_ignore_ascii_case(region) || name.eq_ignore_ascii_case(region)
+            })
+            .map(|(code, _)| *code)
+    }
+}
+
+fn match_postcode(pattern: &str, postcode: &str) -> Option<String> {
+    let mut chars = postcode.chars().peekable();
+    let mut normalised = String::new();
+    for p in pattern.chars() {
+        if p == ' ' {
+            if chars.peek() == Some(&' ') {
+                chars.next();
+            }
+            normalised.push(' ');
+            continue;
+        }
+        let c = chars.next()?;
+        let matches = match p {
+            '9' => c.is_ascii_digit(),
+            'A' => c.is_ascii_alphabetic(),
+            'X' => c.is_ascii_alphanumeric(),
+            p => c == p,
+        };
+        if !matches {
+            return None;
+        }
+        normalised.push(c);
+    }
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(normalised)
+}
+
+/// A postal address. Blank lines are dropped, the region is stored as its code when the country
+/// has a list of regions, and the postcode is in its standard form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AddressValue {
+    pub lines: Vec<String>,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub postcode: Option<String>,
+    pub country: Country,
+}
+
+/// An address block whose required fields, postcode format and regions depend on the selected
+/// country. `Form::parse` returns a `FormError` with an entry for each field (`lines`, `city`,
+/// `region`, `postcode`, `country`) that has a problem, and one for `address` if the validations
+/// fail.
+pub struct Address {
+    lines: Vec<inputs::Text>,
+    city: inputs::Text,
+    region: inputs::Text,
+    postcode: inputs::Text,
+    country: inputs::Select<CountryError, Country>,
+    validations: crate::Validations<AddressValue>,
+}
+
+pub enum AddressMsg {
+    Line(usize, String),
+    City(String),
+    Region(String),
+    Postcode(String),
+    Country(String),
+}
+
+fn text(data: &str) -> inputs::Text {
+    inputs::Text::new(&data.to_string(), crate::Validations::new())
+}
+
+fn non_empty(input: &inputs::Text) -> Option<String> {
+    Some(input.get_input().trim().to_string()).filter(|s| !s.is_empty())
+}
+
+impl Address {
+    /// Shows at least `line_count` address lines, or more if `data` has more.
+    pub fn new(
+        data: AddressValue,
+        line_count: usize,
+        countries: collections::BTreeSet<Country>,
+        validations: crate::Validations<AddressValue>,
+    ) -> Address {
+        let mut lines = data.lines.iter().map(|line| text(line)).collect::<Vec<_>>();
+        while lines.len() < line_count {
+            lines.push(text(""));
+        }
+        Address {
+            lines,
+            city: text(data.city.as_deref().unwrap_or("")),
+            region: text(data.region.as_deref().unwrap_or("")),
+            postcode: text(data.postcode.as_deref().unwrap_or("")),
+            country: inputs::Select::new(data.country, countries),
+            validations,
+        }
+    }
+    pub fn get_lines(&self) -> &[inputs::Text] {
+        &self.lines
+    }
+    pub fn get_city(&self) -> &inputs::Text {
+        &self.city
+    }
+    pub fn get_region(&self) -> &inputs::Text {
+        &self.region
+    }
+    pub fn get_postcode(&self) -> &inputs::Text {
+        &self.postcode
+    }
+    pub fn get_country(&self) -> &inputs::Select<CountryError, Country> {
+        &self.country
+    }
+    pub fn set(&mut self, data: AddressValue) {
+        for (i, input) in self.lines.iter_mut().enumerate() {
+            input.set(data.lines.get(i).cloned().unwrap_or_default());
+        }
+        for line in data.lines.iter().skip(self.lines.len()) {
+            self.lines.push(text(line));
+        }
+        self.city.set(data.city.unwrap_or_default());
+        self.region.set(data.region.unwrap_or_default());
+        self.postcode.set(data.postcode.unwrap_or_default());
+        self.country.set(data.country);
+    }
+    fn required(field: &str) -> crate::Error {
+        crate::Error::Validation(vec![format!("{} is required", field)].into())
+    }
+    fn parse_city(&self, info: &AddressInfo) -> crate::Result<Option<String>> {
+        match non_empty(&self.city) {
+            None if info.city_required() => Err(Address::required("City")),
+            city => Ok(city),
+        }
+    }
+    fn parse_region(&self, country: Country, info: &AddressInfo) -> crate::Result<Option<String>> {
+        match non_empty(&self.region) {
+            None if info.region_required() => Err(Address::required("Region")),
+            Some(region) if !info.regions().is_empty() => info
+                .find_region(&region)
+                .map(|code| Some(code.to_string()))
+                .ok_or_else(|| {
+                    crate::Error::Validation(
+                        vec![format!("{} is not a region of {}", region, country)].into(),
+                    )
+                }),
+            region => Ok(region),
+        }
+    }
+    fn parse_postcode(
+        &self,
+        country: Country,
+        info: &AddressInfo,
+    ) -> crate::Result<Option<String>> {
+        match non_empty(&self.postcode) {
+            None if info.postcode_required() => Err(Address::required("Postcode")),
+            None => Ok(None),
+            Some(_) if !info.has_postcodes() => Err(crate::Error::Validation(
+                vec![format!("{} addresses don't have postcodes", country)].into(),
+            )),
+            Some(postcode) => info.normalise_postcode(&postcode).map(Some).ok_or_else(|| {
+                crate::Error::Validation(
+                    vec![format!(
+                        "{} is not a valid {} postcode, eg {}",
+                        postcode,
+                        country,
+                        info.postcode_example()
+                    )]
+                    .into(),
+                )
+            }),
+        }
+    }
+}
+
+impl crate::Form for Address {
+    type Msg = AddressMsg;
+    type Output = AddressValue;
+    fn update(&mut self, input: Self::Msg) {
+        match input {
+            AddressMsg::Line(i, input) => {
+                if let Some(line) = self.lines.get_mut(i) {
+                    line.update(input);
+                }
+            }
+            AddressMsg::City(input) => self.city.update(input),
+            AddressMsg::Region(input) => self.region.update(input),
+            AddressMsg::Postcode(input) => self.postcode.update(input),
+            AddressMsg::Country(input) => self.country.update(input),
+        }
+    }
+    fn parse(&self) -> crate::FormResult<Self::Output> {
+        let mut errors = crate::FormError::new();
+        let country = match self.country.parse() {
+            Ok(country) => country,
+            Err(e) => {
+                // the other fields can't be checked without a country
+                errors.add_error("country", e);
+                return Err(errors);
+            }
+        };
+        let info = match AddressInfo::of(country) {
+            Some(info) => info,
+            None => {
+                errors.add_error(
+                    "country",
+                    crate::Error::Validation(
+                        vec![format!("Addresses in {} are not supported", country)].into(),
+                    ),
+                );
+                return Err(errors);
+            }
+        };
+        let lines = self.lines.iter().filter_map(non_empty).collect::<Vec<_>>();
+        if lines.is_empty() {
+            errors.add_error("lines", Address::required("Address"));
+        }
+        let city = self.parse_city(info);
+        let region = self.parse_region(country, info);
+        let postcode = self.parse_postcode(country, info);
+        match (city, region, postcode) {
+            (Ok(city), Ok(region), Ok(postcode)) if errors.is_empty() => {
+                let parsed = AddressValue {
+                    lines,
+                    city,
+                    region,
+                    postcode,
+                    country,
+                };
+                if let Err(e) = self.validations.validate(&parsed) {
+                    errors.add_error("address", e.into());
+                    return Err(errors);
+                }
+                Ok(parsed)
+            }
+            (city, region, postcode) => {
+                errors.add_result("city", city);
+                errors.add_result("region", region);
+                errors.add_result("postcode", postcode);
+                Err(errors)
+            }
+        }
+    }
+}
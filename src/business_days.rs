@@ -0,0 +1,378 @@
+use chrono::Datelike;
+use std::{collections, error, fmt, fs, io, path};
+
+#[derive(Debug)]
+pub enum HolidayFileError {
+    Io(io::Error),
+    Line {
+        line: usize,
+        value: String,
+        source: chrono::ParseError,
+    },
+}
+
+impl fmt::Display for HolidayFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HolidayFileError::Io(e) => write!(f, "unable to read holiday file: {}", e),
+            HolidayFileError::Line {
+                line,
+                value,
+                source,
+            } => write!(
+                f,
+                "line {}: {} is not a date in the format YYYY-MM-DD ({})",
+                line, value, source
+            ),
+        }
+    }
+}
+
+impl error::Error for HolidayFileError {}
+
+impl From<io::Error> for HolidayFileError {
+    fn from(e: io::Error) -> HolidayFileError {
+        HolidayFileError::Io(e)
+    }
+}
+
+/// Weekend days plus an explicit set of holidays. Any day that is neither is a business day.
+#[derive(Debug, Clone)]
+pub struct HolidayCalendar {
+    weekend: Vec<chrono::Weekday>,
+    holidays: collections::BTreeMap<chrono::NaiveDate, Option<String>>,
+}
+
+impl Default for HolidayCalendar {
+    fn default() -> HolidayCalendar {
+        HolidayCalendar::new(
+            vec![chrono::Weekday::Sat, chrono::Weekday::Sun],
+            collections::BTreeMap::new(),
+        )
+    }
+}
+
+// keeps the first of any repeated days, so the weekend's length is the number of distinct days
+fn distinct_days(weekend: Vec<chrono::Weekday>) -> Vec<chrono::Weekday> {
+    let mut distinct = Vec::with_capacity(weekend.len());
+    for day in weekend {
+        if !distinct.contains(&day) {
+            distinct.push(day);
+        }
+    }
+    distinct
+}
+
+impl HolidayCalendar {
+    pub fn new(
+        weekend: Vec<chrono::Weekday>,
+        holidays: collections::BTreeMap<chrono::NaiveDate, Option<String>>,
+    ) -> HolidayCalendar {
+        HolidayCalendar {
+            weekend: distinct_days(weekend),
+            holidays,
+        }
+    }
+    /// Parses holidays from text with one holiday per line, as `date` or `date,name` with the
+    /// date formatted as `YYYY-MM-DD`. Blank lines, lines starting with `#` and a `date,...`
+    /// header row before the first holiday are skipped. The weekend is left as Saturday and
+    /// Sunday.
+    pub fn parse_holidays(text: &str) -> Result<HolidayCalendar, HolidayFileError> {
+        let mut holidays = collections::BTreeMap::new();
+        let mut first_row = true;
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(2, ',');
+            let date = fields.next().unwrap_or_default().trim();
+            let header = first_row && date.eq_ignore_ascii_case("date");
+            first_row = false;
+            if header {
+                continue;
+            }
+            let date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|source| {
+                HolidayFileError::Line {
+                    line: idx + 1,
+                    value: date.to_string(),
+                    source,
+                }
+            })?;
+            let name = fields
+                .next()
+                .map(|name| name.trim().trim_matches('"').to_string())
+                .filter(|name| !name.is_empty());
+            holidays.insert(date, name);
+        }
+        Ok(HolidayCalendar {
+            holidays,
+            ..Default::default()
+        })
+    }
+    pub fn from_file(path: impl AsRef<path::Path>) -> Result<HolidayCalendar, HolidayFileError> {
+        HolidayCalendar::parse_holidays(&fs::read_to_string(path)?)
+    }
+    pub fn with_weekend(self, weekend: Vec<chrono::Weekday>) -> HolidayCalendar {
+        HolidayCalendar {
+            weekend: distinct_days(weekend),
+            ..self
+        }
+    }
+    pub fn get_weekend(&self) -> &[chrono::Weekday] {
+        &self.weekend
+    }
+    pub fn is_weekend(&self, date: chrono::NaiveDate) -> bool {
+        self.weekend.contains(&date.weekday())
+    }
+    pub fn is_holiday(&self, date: chrono::NaiveDate) -> bool {
+        self.holidays.contains_key(&date)
+    }
+    pub fn holiday_name(&self, date: chrono::NaiveDate) -> Option<&str> {
+        self.holidays.get(&date).and_then(|name| name.as_deref())
+    }
+    pub fn is_business_day(&self, date: chrono::NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+    /// `date` itself if it is a business day, otherwise the first business day after it. `None`
+    /// if there is no business day before the end of the calendar.
+    pub fn roll_forward(&self, date: chrono::NaiveDate) -> Option<chrono::NaiveDate> {
+        let mut date = date;
+        // a calendar where every weekday is a weekend would never terminate
+        if self.weekend.len() >= 7 {
+            return Some(date);
+        }
+        while !self.is_business_day(date) {
+            date = date.succ_opt()?;
+        }
+        Some(date)
+    }
+    /// The date `days` business days after `date`, skipping weekends and holidays.
+    pub fn add_business_days(
+        &self,
+        date: chrono::NaiveDate,
+        days: u32,
+    ) -> Option<chrono::NaiveDate> {
+        let mut date = date;
+        for _ in 0..days {
+            date = self.roll_forward(date.succ_opt()?)?;
+        }
+        Some(date)
+    }
+    /// The number of business days after `from`, up to and including `to`.
+    pub fn business_days_between(&self, from: chrono::NaiveDate, to: chrono::NaiveDate) -> i64 {
+        let (start, end, sign) = if from <= to {
+            (from, to, 1)
+        } else {
+            (to, from, -1)
+        };
+        let mut count = 0;
+        let mut date = start;
+        while let Some(next) = date.succ_opt().filter(|next| *next <= end) {
+            if self.is_business_day(next) {
+                count += 1;
+            }
+            date = next;
+        }
+        count * sign
+    }
+}
+
+/// Wraps a date input to require business days according to a `HolidayCalendar`.
+///
+/// With `roll` set, a non-business day is rolled forward to the next business day on `parse`
+/// rather than rejected, and `BusinessDateMsg::Normalise` rewrites the raw input to match (eg on
+/// blur). Wrapping the input in a `Calendar` disables non-business days in the grid.
+pub struct BusinessDate<I>
+where
+    I: crate::calendar::CalendarInput,
+{
+    input: I,
+    holidays: HolidayCalendar,
+    roll: bool,
+    min_after: Option<(chrono::NaiveDate, u32)>,
+}
+
+pub enum BusinessDateMsg<I>
+where
+    I: crate::UserInput,
+{
+    Input(I::Input),
+    Normalise,
+}
+
+impl<I> BusinessDate<I>
+where
+    I: crate::calendar::CalendarInput,
+{
+    /// `min_after` is a reference date and the number of business days the input must be at
+    /// least after it, eg a settlement lag.
+    pub fn new(
+        input: I,
+        holidays: HolidayCalendar,
+        roll: bool,
+        min_after: Option<(chrono::NaiveDate, u32)>,
+    ) -> BusinessDate<I> {
+        BusinessDate {
+            input,
+            holidays,
+            roll,
+            min_after,
+        }
+    }
+    pub fn get_input(&self) -> &I {
+        &self.input
+    }
+    pub fn get_holidays(&self) -> &HolidayCalendar {
+        &self.holidays
+    }
+    /// The earliest date allowed by `min_after`, if set.
+    pub fn earliest(&self) -> Option<chrono::NaiveDate> {
+        self.min_after
+            .and_then(|(reference, days)| self.holidays.add_business_days(reference, days))
+    }
+    fn check(&self, date: chrono::NaiveDate) -> crate::ValidationResult {
+        if !self.holidays.is_business_day(date) {
+            return Err(match self.holidays.holiday_name(date) {
+                Some(name) => format!("{} is not a business day ({})", date, name),
+                None => format!("{} is not a business day", date),
+            });
+        }
+        if let Some((reference, days)) = self.min_after {
+            // no earliest date means it would be past the end of the calendar
+            if !matches!(self.earliest(), Some(earliest) if date >= earliest) {
+                return Err(format!(
+                    "Date should be at least {} business days after {} but was {}",
+                    days, reference, date
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<I> crate::UserInput for BusinessDate<I>
+where
+    I: crate::calendar::CalendarInput,
+{
+    type Output = I::Output;
+    type Input = BusinessDateMsg<I>;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            BusinessDateMsg::Input(input) => self.input.update(input),
+            BusinessDateMsg::Normalise if self.roll => {
+                if let Ok(parsed) = self.input.parse() {
+                    let date = I::to_date(&parsed);
+                    if let Some(rolled) = self.holidays.roll_forward(date) {
+                        if rolled != date {
+                            self.input.set(I::from_date(rolled));
+                        }
+                    }
+                }
+            }
+            BusinessDateMsg::Normalise => {}
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = self.input.parse()?;
+        let date = I::to_date(&parsed);
+        let invalid = |e: String| crate::Error::Validation(vec![e].into());
+        let date = match self.holidays.roll_forward(date) {
+            Some(rolled) if self.roll && rolled != date => {
+                // the inner input only validated the date before it was rolled
+                if !self.input.is_valid_date(rolled) {
+                    return Err(invalid(format!(
+                        "{} is not a business day and the next one, {}, is not allowed",
+                        date, rolled
+                    )));
+                }
+                rolled
+            }
+            _ => date,
+        };
+        self.check(date).map_err(invalid)?;
+        Ok(I::from_date(date))
+    }
+}
+
+impl<I> crate::SetInput for BusinessDate<I>
+where
+    I: crate::calendar::CalendarInput,
+{
+    fn set(&mut self, data: Self::Output) {
+        self.input.set(data);
+    }
+}
+
+impl<I> crate::calendar::CalendarInput for BusinessDate<I>
+where
+    I: crate::calendar::CalendarInput,
+{
+    fn to_date(data: &Self::Output) -> chrono::NaiveDate {
+        I::to_date(data)
+    }
+    fn from_date(date: chrono::NaiveDate) -> Self::Output {
+        I::from_date(date)
+    }
+    fn is_valid_date(&self, date: chrono::NaiveDate) -> bool {
+        self.input.is_valid_date(date) && self.check(date).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UserInput;
+
+    fn date(text: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    // Friday 2021-10-15 is the latest allowed date
+    fn before_weekend(input: &chrono::NaiveDate) -> crate::ValidationResult {
+        if *input <= date("2021-10-15") {
+            Ok(())
+        } else {
+            Err(format!("{} is too late", input))
+        }
+    }
+
+    #[test]
+    fn rolls_forward() {
+        let holidays = HolidayCalendar::parse_holidays("date,name\n2021-10-18,Holiday").unwrap();
+        assert_eq!(
+            holidays.roll_forward(date("2021-10-16")),
+            Some(date("2021-10-19"))
+        );
+        assert_eq!(
+            holidays.add_business_days(date("2021-10-15"), 2),
+            Some(date("2021-10-20"))
+        );
+        assert_eq!(
+            holidays.business_days_between(date("2021-10-20"), date("2021-10-15")),
+            -2
+        );
+    }
+
+    #[test]
+    fn end_of_calendar() {
+        let last = chrono::naive::MAX_DATE;
+        let holidays = HolidayCalendar::new(Vec::new(), vec![(last, None)].into_iter().collect());
+        assert_eq!(holidays.roll_forward(last), None);
+        assert_eq!(holidays.add_business_days(last.pred(), 1), None);
+        assert_eq!(holidays.business_days_between(last.pred().pred(), last), 1);
+    }
+
+    #[test]
+    fn rolled_dates_are_validated_by_the_input() {
+        let input = crate::inputs::NaiveDate::new(
+            date("2021-10-15"),
+            "%Y-%m-%d",
+            crate::Validations::from_vec(vec![before_weekend]),
+        );
+        let mut business = BusinessDate::new(input, HolidayCalendar::default(), true, None);
+        assert_eq!(business.parse().unwrap(), date("2021-10-15"));
+        business.update(BusinessDateMsg::Input("2021-10-16".to_string()));
+        assert!(business.parse().is_err());
+    }
+}
@@ -0,0 +1,344 @@
+use chrono::Datelike;
+use resolution::DateResolution as DateResolutionTrait;
+use resolution::TimeResource as TimeResourceTrait;
+
+/// Date inputs that can back a `Calendar`.
+pub trait CalendarInput: crate::SetInput {
+    fn to_date(data: &Self::Output) -> chrono::NaiveDate;
+    fn from_date(date: chrono::NaiveDate) -> Self::Output;
+    fn is_valid_date(&self, date: chrono::NaiveDate) -> bool;
+}
+
+impl CalendarInput for crate::inputs::NaiveDate {
+    fn to_date(data: &chrono::NaiveDate) -> chrono::NaiveDate {
+        *data
+    }
+    fn from_date(date: chrono::NaiveDate) -> chrono::NaiveDate {
+        date
+    }
+    fn is_valid_date(&self, date: chrono::NaiveDate) -> bool {
+        self.is_valid(&date)
+    }
+}
+
+impl CalendarInput for crate::inputs::Date {
+    fn to_date(data: &resolution::Date) -> chrono::NaiveDate {
+        data.start()
+    }
+    fn from_date(date: chrono::NaiveDate) -> resolution::Date {
+        date.into()
+    }
+    fn is_valid_date(&self, date: chrono::NaiveDate) -> bool {
+        self.is_valid(&date.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarDay {
+    pub date: chrono::NaiveDate,
+    pub selected: bool,
+    pub today: bool,
+    pub outside_month: bool,
+    pub disabled: bool,
+}
+
+/// Model for rendering a date picker over a date input. The calendar tracks which month is being
+/// viewed separately from the input's value, so the user can browse without changing the
+/// selection. Days are disabled when they fall outside `min`/`max` or fail the input's
+/// validations.
+pub struct Calendar<I>
+where
+    I: CalendarInput,
+{
+    input: I,
+    viewed: resolution::Month,
+    min: Option<chrono::NaiveDate>,
+    max: Option<chrono::NaiveDate>,
+    week_start: chrono::Weekday,
+}
+
+pub enum CalendarMsg<I>
+where
+    I: crate::UserInput,
+{
+    Input(I::Input),
+    Select(chrono::NaiveDate),
+    View(resolution::Month),
+    NextMonth,
+    PreviousMonth,
+    NextYear,
+    PreviousYear,
+}
+
+impl<I> Calendar<I>
+where
+    I: CalendarInput,
+{
+    /// The viewed month starts as the month of the input's value, or the current month if the
+    /// input doesn't parse.
+    pub fn new(
+        input: I,
+        min: Option<chrono::NaiveDate>,
+        max: Option<chrono::NaiveDate>,
+        week_start: chrono::Weekday,
+    ) -> Calendar<I> {
+        let viewed = resolution::Month::from_date(
+            input
+                .parse()
+                .map(|data| I::to_date(&data))
+                .unwrap_or_else(|_| chrono::Utc::now().date().naive_utc()),
+        );
+        Calendar {
+            input,
+            viewed,
+            min,
+            max,
+            week_start,
+        }
+    }
+    pub fn get_input(&self) -> &I {
+        &self.input
+    }
+    pub fn get_viewed(&self) -> resolution::Month {
+        self.viewed
+    }
+    pub fn get_week_start(&self) -> chrono::Weekday {
+        self.week_start
+    }
+    pub fn is_disabled(&self, date: chrono::NaiveDate) -> bool {
+        matches!(self.min, Some(min) if date < min)
+            || matches!(self.max, Some(max) if date > max)
+            || !self.input.is_valid_date(date)
+    }
+    /// The weekdays in display order, for column headers.
+    pub fn weekdays(&self) -> [chrono::Weekday; 7] {
+        let mut days = [self.week_start; 7];
+        for i in 1..7 {
+            days[i] = days[i - 1].succ();
+        }
+        days
+    }
+    /// The weeks covering the viewed month, each starting on `week_start`. Days from the
+    /// neighbouring months that fill out the first and last weeks are flagged `outside_month`.
+    pub fn weeks(&self, today: chrono::NaiveDate) -> Vec<[CalendarDay; 7]> {
+        let first = self.viewed.start();
+        let last = self.viewed.succ().start().pred();
+        let selected = self.input.parse().ok().map(|data| I::to_date(&data));
+        let offset = (7 + first.weekday().num_days_from_monday()
+            - self.week_start.num_days_from_monday())
+            % 7;
+        let mut day = first - chrono::Duration::days(i64::from(offset));
+        let mut weeks = Vec::new();
+        while day <= last {
+            let mut week = [CalendarDay {
+                date: day,
+                selected: false,
+                today: false,
+                outside_month: false,
+                disabled: false,
+            }; 7];
+            for cell in week.iter_mut() {
+                *cell = CalendarDay {
+                    date: day,
+                    selected: selected == Some(day),
+                    today: day == today,
+                    outside_month: day < first || day > last,
+                    disabled: self.is_disabled(day),
+                };
+                day = day.succ();
+            }
+            weeks.push(week);
+        }
+        weeks
+    }
+    fn view_year_offset(&mut self, years: i32) {
+        let first = self.viewed.start();
+        self.viewed = resolution::Month::from_date(chrono::NaiveDate::from_ymd(
+            first.year() + years,
+            first.month(),
+            1,
+        ));
+    }
+}
+
+impl<I> crate::UserInput for Calendar<I>
+where
+    I: CalendarInput,
+{
+    type Output = I::Output;
+    type Input = CalendarMsg<I>;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            CalendarMsg::Input(input) => {
+                self.input.update(input);
+                if let Ok(data) = self.input.parse() {
+                    self.viewed = resolution::Month::from_date(I::to_date(&data));
+                }
+            }
+            CalendarMsg::Select(date) => {
+                if !self.is_disabled(date) {
+                    self.input.set(I::from_date(date));
+                    self.viewed = resolution::Month::from_date(date);
+                }
+            }
+            CalendarMsg::View(month) => self.viewed = month,
+            CalendarMsg::NextMonth => self.viewed = self.viewed.succ(),
+            CalendarMsg::PreviousMonth => self.viewed = self.viewed.pred(),
+            CalendarMsg::NextYear => self.view_year_offset(1),
+            CalendarMsg::PreviousYear => self.view_year_offset(-1),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = self.input.parse()?;
+        let date = I::to_date(&parsed);
+        crate::check_bounds(
+            "Date",
+            ("on or after", "on or before"),
+            &date,
+            (self.min.as_ref(), self.max.as_ref()),
+            ToString::to_string,
+        )
+        .map_err(|e| crate::Error::Validation(vec![e].into()))?;
+        Ok(parsed)
+    }
+}
+
+/// Period inputs that can back a `PeriodPicker`.
+pub trait PeriodPickerInput: crate::SetInput {
+    fn is_valid_period(&self, period: &Self::Output) -> bool;
+}
+
+impl PeriodPickerInput for crate::inputs::Month {
+    fn is_valid_period(&self, period: &resolution::Month) -> bool {
+        self.is_valid(period)
+    }
+}
+
+impl PeriodPickerInput for crate::inputs::Quarter {
+    fn is_valid_period(&self, period: &resolution::Quarter) -> bool {
+        self.is_valid(period)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodCell<R> {
+    pub period: R,
+    pub selected: bool,
+    pub disabled: bool,
+}
+
+/// Model for rendering a grid of the months or quarters in a year, the period equivalent of
+/// `Calendar`.
+pub struct PeriodPicker<I>
+where
+    I: PeriodPickerInput,
+    I::Output: resolution::DateResolution,
+{
+    input: I,
+    viewed: resolution::Year,
+    min: Option<I::Output>,
+    max: Option<I::Output>,
+}
+
+pub type MonthPicker = PeriodPicker<crate::inputs::Month>;
+pub type QuarterPicker = PeriodPicker<crate::inputs::Quarter>;
+
+pub enum PeriodPickerMsg<I>
+where
+    I: crate::UserInput,
+{
+    Input(I::Input),
+    Select(I::Output),
+    View(resolution::Year),
+    NextYear,
+    PreviousYear,
+}
+
+impl<I> PeriodPicker<I>
+where
+    I: PeriodPickerInput,
+    I::Output: resolution::DateResolution,
+{
+    /// The viewed year starts as the year of the input's value, or the current year if the
+    /// input doesn't parse.
+    pub fn new(input: I, min: Option<I::Output>, max: Option<I::Output>) -> PeriodPicker<I> {
+        let viewed = resolution::Year::new(
+            input
+                .parse()
+                .map(|period| period.start().year())
+                .unwrap_or_else(|_| chrono::Utc::now().year()),
+        );
+        PeriodPicker {
+            input,
+            viewed,
+            min,
+            max,
+        }
+    }
+    pub fn get_input(&self) -> &I {
+        &self.input
+    }
+    pub fn get_viewed(&self) -> resolution::Year {
+        self.viewed
+    }
+    pub fn is_disabled(&self, period: &I::Output) -> bool {
+        matches!(&self.min, Some(min) if period < min)
+            || matches!(&self.max, Some(max) if period > max)
+            || !self.input.is_valid_period(period)
+    }
+    /// The periods starting in the viewed year, in order.
+    pub fn cells(&self) -> Vec<PeriodCell<I::Output>> {
+        let selected = self.input.parse().ok();
+        let mut period = I::Output::from_date(self.viewed.start());
+        let mut cells = Vec::new();
+        while period.start().year() == self.viewed.year_num() {
+            cells.push(PeriodCell {
+                period,
+                selected: selected == Some(period),
+                disabled: self.is_disabled(&period),
+            });
+            period = period.succ();
+        }
+        cells
+    }
+}
+
+impl<I> crate::UserInput for PeriodPicker<I>
+where
+    I: PeriodPickerInput,
+    I::Output: resolution::DateResolution,
+{
+    type Output = I::Output;
+    type Input = PeriodPickerMsg<I>;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            PeriodPickerMsg::Input(input) => {
+                self.input.update(input);
+                if let Ok(period) = self.input.parse() {
+                    self.viewed = resolution::Year::new(period.start().year());
+                }
+            }
+            PeriodPickerMsg::Select(period) => {
+                if !self.is_disabled(&period) {
+                    self.viewed = resolution::Year::new(period.start().year());
+                    self.input.set(period);
+                }
+            }
+            PeriodPickerMsg::View(year) => self.viewed = year,
+            PeriodPickerMsg::NextYear => self.viewed = self.viewed.succ(),
+            PeriodPickerMsg::PreviousYear => self.viewed = self.viewed.pred(),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = self.input.parse()?;
+        crate::check_bounds(
+            "Value",
+            ("at least", "at most"),
+            &parsed,
+            (self.min.as_ref(), self.max.as_ref()),
+            ToString::to_string,
+        )
+        .map_err(|e| crate::Error::Validation(vec![e].into()))?;
+        Ok(parsed)
+    }
+}
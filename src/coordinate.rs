@@ -0,0 +1,424 @@
+use std::{error, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Latitude,
+    Longitude,
+}
+
+impl Axis {
+    fn max(&self) -> f64 {
+        match self {
+            Axis::Latitude => 90.0,
+            Axis::Longitude => 180.0,
+        }
+    }
+    fn hemispheres(&self) -> (char, char) {
+        match self {
+            Axis::Latitude => ('N', 'S'),
+            Axis::Longitude => ('E', 'W'),
+        }
+    }
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Axis::Latitude => write!(f, "latitude"),
+            Axis::Longitude => write!(f, "longitude"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoordinateError {
+    Empty,
+    /// Couldn't tell where the latitude ends and the longitude starts.
+    Ambiguous,
+    Missing(Axis),
+    InvalidNumber {
+        axis: Axis,
+        input: String,
+    },
+    TooManyParts {
+        axis: Axis,
+        input: String,
+    },
+    MinutesOutOfRange {
+        axis: Axis,
+        minutes: f64,
+    },
+    SecondsOutOfRange {
+        axis: Axis,
+        seconds: f64,
+    },
+    WrongHemisphere {
+        axis: Axis,
+        hemisphere: char,
+    },
+    SignAndHemisphere(Axis),
+    OutOfRange {
+        axis: Axis,
+        degrees: f64,
+    },
+}
+
+impl fmt::Display for CoordinateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoordinateError::Empty => write!(f, "no coordinate was entered"),
+            CoordinateError::Ambiguous => write!(
+                f,
+                "separate the latitude and longitude with a comma, eg -33.86, 151.21"
+            ),
+            CoordinateError::Missing(axis) => write!(f, "the {} is missing", axis),
+            CoordinateError::InvalidNumber { axis, input } => {
+                write!(f, "'{}' is not a valid {}", input, axis)
+            }
+            CoordinateError::TooManyParts { axis, input } => write!(
+                f,
+                "the {} '{}' should be at most degrees, minutes and seconds",
+                axis, input
+            ),
+            CoordinateError::MinutesOutOfRange { axis, minutes } => write!(
+                f,
+                "the {} minutes should be less than 60 but were {}",
+                axis, minutes
+            ),
+            CoordinateError::SecondsOutOfRange { axis, seconds } => write!(
+                f,
+                "the {} seconds should be less than 60 but were {}",
+                axis, seconds
+            ),
+            CoordinateError::WrongHemisphere { axis, hemisphere } => {
+                let (positive, negative) = axis.hemispheres();
+                write!(
+                    f,
+                    "the {} should be {} or {} but was {}",
+                    axis, positive, negative, hemisphere
+                )
+            }
+            CoordinateError::SignAndHemisphere(axis) => write!(
+                f,
+                "the {} should have either a minus sign or a hemisphere, not both",
+                axis
+            ),
+            CoordinateError::OutOfRange { axis, degrees } => write!(
+                f,
+                "the {} should be between -{max} and {max} but was {}",
+                axis,
+                degrees,
+                max = axis.max()
+            ),
+        }
+    }
+}
+
+impl error::Error for CoordinateError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoordinateFormat {
+    /// eg `-33.8600, 151.2100`
+    DecimalDegrees { places: usize },
+    /// eg `33°51'36"S 151°12'36"E`. At most `MAX_SECOND_PLACES` are shown.
+    DegreesMinutesSeconds { second_places: usize },
+}
+
+/// The most places of seconds that are shown, as the seconds are scaled to whole units of the
+/// last place and more would overflow.
+pub const MAX_SECOND_PLACES: usize = 9;
+
+impl CoordinateFormat {
+    pub fn format(&self, point: (f64, f64)) -> String {
+        let (latitude, longitude) = point;
+        match self {
+            CoordinateFormat::DecimalDegrees { places } => {
+                format!("{:.*}, {:.*}", places, latitude, places, longitude)
+            }
+            CoordinateFormat::DegreesMinutesSeconds { second_places } => format!(
+                "{} {}",
+                format_dms(latitude, Axis::Latitude, *second_places),
+                format_dms(longitude, Axis::Longitude, *second_places)
+            ),
+        }
+    }
+}
+
+fn format_dms(degrees: f64, axis: Axis, second_places: usize) -> String {
+    let (positive, negative) = axis.hemispheres();
+    let hemisphere = if degrees < 0.0 { negative } else { positive };
+    let second_places = second_places.min(MAX_SECOND_PLACES);
+    // round once in whole units of the last shown place, so eg 59.9999" carries into the minutes
+    let scale = 10_u64.pow(second_places as u32);
+    let units = (degrees.abs() * 3600.0 * scale as f64).round() as u64;
+    let seconds = units % (60 * scale);
+    let minutes = units / (60 * scale) % 60;
+    let whole_degrees = units / (3600 * scale);
+    let seconds = if second_places == 0 {
+        seconds.to_string()
+    } else {
+        format!(
+            "{}.{:0width$}",
+            seconds / scale,
+            seconds % scale,
+            width = second_places
+        )
+    };
+    format!("{}°{}'{}\"{}", whole_degrees, minutes, seconds, hemisphere)
+}
+
+/// Parses a `(latitude, longitude)` point in decimal degrees (`-33.86, 151.21`) or degrees,
+/// minutes and seconds (`33°51'36"S 151°12'36"E`, or with the hemisphere first). Minutes and
+/// seconds may also be separated by spaces or colons, and the last part may have a fraction, eg
+/// `33 51.6 S, 151 12.6 E`.
+pub fn parse_coordinate(input: &str) -> Result<(f64, f64), CoordinateError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(CoordinateError::Empty);
+    }
+    let (latitude, longitude) = split_coordinate(input)?;
+    Ok((
+        parse_axis(latitude, Axis::Latitude)?,
+        parse_axis(longitude, Axis::Longitude)?,
+    ))
+}
+
+fn split_coordinate(input: &str) -> Result<(&str, &str), CoordinateError> {
+    if let Some((latitude, longitude)) = input.split_once(',') {
+        return Ok((latitude, longitude));
+    }
+    let upper = input.to_ascii_uppercase();
+    if let Some(i) = upper.find(['N', 'S'].as_ref()) {
+        let before = upper[..i].trim();
+        // a hemisphere after the latitude ends it, otherwise it starts it and the longitude
+        // starts with its own hemisphere
+        let split = if before.is_empty() {
+            upper[i..]
+                .find(['E', 'W'].as_ref())
+                .map(|j| i + j)
+                .ok_or(CoordinateError::Missing(Axis::Longitude))?
+        } else {
+            i + 1
+        };
+        return Ok((&input[..split], &input[split..]));
+    }
+    let mut parts = input.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(latitude), Some(longitude), None) => Ok((latitude, longitude)),
+        (Some(_), None, None) => Err(CoordinateError::Missing(Axis::Longitude)),
+        _ => Err(CoordinateError::Ambiguous),
+    }
+}
+
+fn parse_axis(input: &str, axis: Axis) -> Result<f64, CoordinateError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(CoordinateError::Missing(axis));
+    }
+    let (positive, negative) = axis.hemispheres();
+    let first = input.chars().next().map(|c| c.to_ascii_uppercase());
+    let last = input.chars().last().map(|c| c.to_ascii_uppercase());
+    let (hemisphere, numbers) = match (first, last) {
+        (Some(c), _) if c.is_ascii_alphabetic() => (Some(c), &input[1..]),
+        (_, Some(c)) if c.is_ascii_alphabetic() => (Some(c), &input[..input.len() - 1]),
+        _ => (None, input),
+    };
+    if let Some(hemisphere) = hemisphere.filter(|h| *h != positive && *h != negative) {
+        return Err(CoordinateError::WrongHemisphere { axis, hemisphere });
+    }
+    let numbers = numbers.trim();
+    let (sign, numbers) = match numbers.strip_prefix(|c| c == '-' || c == '−') {
+        Some(_) if hemisphere.is_some() => return Err(CoordinateError::SignAndHemisphere(axis)),
+        Some(rest) => (-1.0, rest),
+        None => (1.0, numbers.strip_prefix('+').unwrap_or(numbers)),
+    };
+    let invalid = || CoordinateError::InvalidNumber {
+        axis,
+        input: input.to_string(),
+    };
+    let parts = numbers
+        .split(|c: char| c.is_whitespace() || "°'\":,".contains(c))
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            // `f64::from_str` would also take eg `1e5` and `inf`
+            if part.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                part.parse::<f64>().map_err(|_| invalid())
+            } else {
+                Err(invalid())
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // only the last part may have a fraction, eg `33°51.6'` but not `33.5°51'`
+    if parts.iter().rev().skip(1).any(|part| part.fract() != 0.0) {
+        return Err(invalid());
+    }
+    let (degrees, minutes, seconds) = match parts.as_slice() {
+        [] => return Err(invalid()),
+        [degrees] => (*degrees, 0.0, 0.0),
+        [degrees, minutes] => (*degrees, *minutes, 0.0),
+        [degrees, minutes, seconds] => (*degrees, *minutes, *seconds),
+        _ => {
+            return Err(CoordinateError::TooManyParts {
+                axis,
+                input: input.to_string(),
+            })
+        }
+    };
+    if minutes >= 60.0 {
+        return Err(CoordinateError::MinutesOutOfRange { axis, minutes });
+    }
+    if seconds >= 60.0 {
+        return Err(CoordinateError::SecondsOutOfRange { axis, seconds });
+    }
+    let sign = if hemisphere == Some(negative) {
+        -1.0
+    } else {
+        sign
+    };
+    let degrees = sign * (degrees + minutes / 60.0 + seconds / 3600.0);
+    if degrees.abs() > axis.max() {
+        return Err(CoordinateError::OutOfRange { axis, degrees });
+    }
+    Ok(degrees)
+}
+
+/// A `(latitude, longitude)` point in either decimal degrees or degrees, minutes and seconds.
+/// `CoordinateMsg::Normalise` rewrites the raw input in the display format (eg on blur).
+/// Errors are a `CoordinateError` in `Error::Parse`.
+pub struct Coordinate {
+    input: String,
+    display: CoordinateFormat,
+    validations: crate::Validations<(f64, f64)>,
+}
+
+pub enum CoordinateMsg {
+    Input(String),
+    Normalise,
+}
+
+impl Coordinate {
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+    pub fn new(
+        data: (f64, f64),
+        display: CoordinateFormat,
+        validations: crate::Validations<(f64, f64)>,
+    ) -> Coordinate {
+        Coordinate {
+            input: display.format(data),
+            display,
+            validations,
+        }
+    }
+    pub fn get_display(&self) -> CoordinateFormat {
+        self.display
+    }
+    /// Changes the display format, rewriting the raw input if it currently parses.
+    pub fn set_display(&mut self, display: CoordinateFormat) {
+        self.display = display;
+        if let Ok(point) = parse_coordinate(&self.input) {
+            self.input = display.format(point);
+        }
+    }
+}
+
+impl crate::SetInput for Coordinate {
+    fn set(&mut self, data: (f64, f64)) {
+        self.input = self.display.format(data);
+    }
+}
+
+impl crate::UserInput for Coordinate {
+    type Output = (f64, f64);
+    type Input = CoordinateMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            CoordinateMsg::Input(input) => self.input = input,
+            CoordinateMsg::Normalise => {
+                if let Ok(point) = parse_coordinate(&self.input) {
+                    self.input = self.display.format(point);
+                }
+            }
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = parse_coordinate(&self.input)?;
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses() {
+        let expected = (-33.86, 151.21);
+        for input in [
+            "-33.86, 151.21",
+            "33°51'36\"S 151°12'36\"E",
+            "S33°51'36\" E151°12'36\"",
+            "33 51.6 S, 151 12.6 E",
+            "-33:51:36, 151:12:36",
+        ]
+        .iter()
+        {
+            let (latitude, longitude) = parse_coordinate(input).unwrap();
+            assert!((latitude - expected.0).abs() < 1e-9, "{}", input);
+            assert!((longitude - expected.1).abs() < 1e-9, "{}", input);
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let point = (-33.86, 151.21);
+        for format in [
+            CoordinateFormat::DecimalDegrees { places: 4 },
+            CoordinateFormat::DegreesMinutesSeconds { second_places: 0 },
+            CoordinateFormat::DegreesMinutesSeconds { second_places: 2 },
+        ]
+        .iter()
+        {
+            let (latitude, longitude) = parse_coordinate(&format.format(point)).unwrap();
+            assert!((latitude - point.0).abs() < 1e-4);
+            assert!((longitude - point.1).abs() < 1e-4);
+        }
+        assert_eq!(
+            CoordinateFormat::DegreesMinutesSeconds { second_places: 0 }.format(point),
+            "33°51'36\"S 151°12'36\"E"
+        );
+    }
+
+    #[test]
+    fn clamps_second_places() {
+        let format = CoordinateFormat::DegreesMinutesSeconds { second_places: 30 };
+        assert_eq!(
+            format.format((0.5, -0.5)),
+            "0°30'0.000000000\"N 0°30'0.000000000\"W"
+        );
+    }
+
+    #[test]
+    fn rejects() {
+        for input in [
+            "",
+            "33abc51, 151",
+            "1e5, 151",
+            "inf, 151",
+            "33.5°51', 151",
+            "33°60', 151",
+            "91, 0",
+            "0, 181",
+            "-33 S, 151",
+            "33 E, 151",
+            "1 2 3 4, 151",
+            "-33.86",
+        ]
+        .iter()
+        {
+            assert!(parse_coordinate(input).is_err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::{collections, error, fmt, str};
+
+#[derive(Debug)]
+pub struct CountryError {
+    code: String,
+}
+
+impl CountryError {
+    pub fn new(code: String) -> CountryError {
+        CountryError { code }
+    }
+}
+
+impl fmt::Display for CountryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a supported country code", self.code)
+    }
+}
+
+impl error::Error for CountryError {}
+
+// sorted by code. The phone and address modules have their own tables of metadata for these
+// countries.
+const COUNTRIES: &[(&str, &str)] = &[
+    ("AU", "Australia"),
+    ("BR", "Brazil"),
+    ("CA", "Canada"),
+    ("CN", "China"),
+    ("DE", "Germany"),
+    ("ES", "Spain"),
+    ("FR", "France"),
+    ("GB", "United Kingdom"),
+    ("IE", "Ireland"),
+    ("IN", "India"),
+    ("IT", "Italy"),
+    ("JP", "Japan"),
+    ("MX", "Mexico"),
+    ("NL", "Netherlands"),
+    ("NZ", "New Zealand"),
+    ("SG", "Singapore"),
+    ("US", "United States"),
+    ("ZA", "South Africa"),
+];
+
+/// A country from the built-in table, by ISO-3166 alpha-2 code. The same type is used by phone
+/// numbers and addresses, so one country select can drive both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Country {
+    code: &'static str,
+}
+
+impl Country {
+    pub fn all() -> collections::BTreeSet<Country> {
+        COUNTRIES.iter().map(|(code, _)| Country { code }).collect()
+    }
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+    /// The English short name, eg `United Kingdom`.
+    pub fn name(&self) -> &'static str {
+        COUNTRIES
+            .iter()
+            .find(|(code, _)| *code == self.code)
+            .map(|(_, name)| *name)
+            .expect("countries are only created from the table")
+    }
+}
+
+impl fmt::Display for Country {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl str::FromStr for Country {
+    type Err = CountryError;
+    fn from_str(s: &str) -> Result<Country, CountryError> {
+        let code = s.trim();
+        COUNTRIES
+            .iter()
+            .find(|(c, _)| c.eq_ignore_ascii_case(code))
+            .map(|(code, _)| Country { code })
+            .ok_or_else(|| CountryError::new(s.to_string()))
+    }
+}
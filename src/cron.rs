@@ -0,0 +1,373 @@
+use chrono::{Datelike, Timelike};
+use std::{collections, error, fmt, str};
+
+#[derive(Debug)]
+pub struct CronError {
+    reason: String,
+}
+
+impl CronError {
+    pub fn new(reason: String) -> CronError {
+        CronError { reason }
+    }
+}
+
+impl fmt::Display for CronError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl error::Error for CronError {}
+
+struct Field {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    // names for the values starting from `min`
+    names: &'static [&'static str],
+}
+
+const SECOND: Field = Field {
+    name: "second",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const MINUTE: Field = Field {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR: Field = Field {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY_OF_MONTH: Field = Field {
+    name: "day of month",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH: Field = Field {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &[
+        "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+    ],
+};
+// 7 is accepted as a second Sunday, as most crons do
+const DAY_OF_WEEK: Field = Field {
+    name: "day of week",
+    min: 0,
+    max: 7,
+    names: &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"],
+};
+
+impl Field {
+    fn value(&self, text: &str) -> Result<u32, CronError> {
+        let value = match self
+            .names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(text))
+        {
+            Some(idx) => self.min + idx as u32,
+            None => text
+                .parse::<u32>()
+                .map_err(|_| CronError::new(format!("{} {} is not a number", self.name, text)))?,
+        };
+        if value < self.min || value > self.max {
+            return Err(CronError::new(format!(
+                "{} {} out of range ({}-{})",
+                self.name, value, self.min, self.max
+            )));
+        }
+        Ok(value)
+    }
+    fn parse(&self, text: &str) -> Result<collections::BTreeSet<u32>, CronError> {
+        let mut values = collections::BTreeSet::new();
+        for part in text.split(',') {
+            let mut range_step = part.splitn(2, '/');
+            let range = range_step.next().unwrap_or_default();
+            let step = match range_step.next() {
+                Some(step) => match step.parse::<u32>() {
+                    Ok(step) if step > 0 => Some(step),
+                    _ => {
+                        return Err(CronError::new(format!(
+                            "{} step {} should be a number greater than zero",
+                            self.name, step
+                        )))
+                    }
+                },
+                None => None,
+            };
+            let (low, high) = if range == "*" || range == "?" {
+                (self.min, self.max)
+            } else if let Some(idx) = range.find('-') {
+                (self.value(&range[..idx])?, self.value(&range[idx + 1..])?)
+            } else if range.is_empty() {
+                return Err(CronError::new(format!(
+                    "{} has an empty entry in {}",
+                    self.name, text
+                )));
+            } else {
+                let value = self.value(range)?;
+                // `5/15` is shorthand for `5-max/15`
+                (value, if step.is_some() { self.max } else { value })
+            };
+            if low > high {
+                return Err(CronError::new(format!(
+                    "{} range {}-{} is backwards",
+                    self.name, low, high
+                )));
+            }
+            values.extend((low..=high).step_by(step.unwrap_or(1) as usize));
+        }
+        Ok(values)
+    }
+}
+
+/// A parsed cron expression, in the standard 5 field (`minute hour day-of-month month
+/// day-of-week`) or 6 field (with a leading `second`) syntax, or one of the `@daily` style
+/// shorthands.
+///
+/// As with most crons, when both day of month and day of week are restricted a day matching
+/// either is a match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    seconds: collections::BTreeSet<u32>,
+    minutes: collections::BTreeSet<u32>,
+    hours: collections::BTreeSet<u32>,
+    days_of_month: collections::BTreeSet<u32>,
+    months: collections::BTreeSet<u32>,
+    /// numbered from Sunday as 0
+    days_of_week: collections::BTreeSet<u32>,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn get_seconds(&self) -> &collections::BTreeSet<u32> {
+        &self.seconds
+    }
+    pub fn get_minutes(&self) -> &collections::BTreeSet<u32> {
+        &self.minutes
+    }
+    pub fn get_hours(&self) -> &collections::BTreeSet<u32> {
+        &self.hours
+    }
+    pub fn get_days_of_month(&self) -> &collections::BTreeSet<u32> {
+        &self.days_of_month
+    }
+    pub fn get_months(&self) -> &collections::BTreeSet<u32> {
+        &self.months
+    }
+    pub fn get_days_of_week(&self) -> &collections::BTreeSet<u32> {
+        &self.days_of_week
+    }
+    pub fn matches_date(&self, date: chrono::NaiveDate) -> bool {
+        let day_of_month = self.days_of_month.contains(&date.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(&date.weekday().num_days_from_sunday());
+        let day = match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            (true, false) => day_of_month,
+            (false, true) => day_of_week,
+            (false, false) => true,
+        };
+        day && self.months.contains(&date.month())
+    }
+    pub fn matches(&self, datetime: chrono::NaiveDateTime) -> bool {
+        self.matches_date(datetime.date())
+            && self.hours.contains(&datetime.hour())
+            && self.minutes.contains(&datetime.minute())
+            && self.seconds.contains(&datetime.second())
+    }
+    /// The first fire time strictly after `after`, if there is one within the next 28 years (a
+    /// full cycle of the calendar, so schedules such as `0 0 29 2 *` are found).
+    pub fn next_after(&self, after: chrono::NaiveDateTime) -> Option<chrono::NaiveDateTime> {
+        let start = after
+            .with_nanosecond(0)?
+            .checked_add_signed(chrono::Duration::seconds(1))?;
+        // near the end of chrono's calendar search up to its last day instead
+        let last = start
+            .date()
+            .checked_add_signed(chrono::Duration::days(28 * 366))
+            .unwrap_or(chrono::naive::MAX_DATE);
+        let mut date = start.date();
+        while date <= last {
+            if self.matches_date(date) {
+                let from = if date == start.date() {
+                    start.time()
+                } else {
+                    chrono::NaiveTime::from_hms(0, 0, 0)
+                };
+                for hour in self.hours.range(from.hour()..) {
+                    for minute in &self.minutes {
+                        for second in &self.seconds {
+                            let time = chrono::NaiveTime::from_hms(*hour, *minute, *second);
+                            if time >= from {
+                                return Some(date.and_time(time));
+                            }
+                        }
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+    /// Up to `n` fire times after `after`, eg to preview a schedule.
+    pub fn upcoming(&self, after: chrono::NaiveDateTime, n: usize) -> Vec<chrono::NaiveDateTime> {
+        let mut times = Vec::new();
+        let mut current = after;
+        while times.len() < n {
+            match self.next_after(current) {
+                Some(next) => {
+                    times.push(next);
+                    current = next;
+                }
+                None => break,
+            }
+        }
+        times
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl str::FromStr for CronSchedule {
+    type Err = CronError;
+    fn from_str(s: &str) -> Result<CronSchedule, CronError> {
+        let trimmed = s.trim();
+        let expanded = match trimmed.to_ascii_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other if other.starts_with('@') => {
+                return Err(CronError::new(format!(
+                    "{} is not a known shorthand",
+                    trimmed
+                )))
+            }
+            _ => trimmed,
+        };
+        let fields = expanded.split_whitespace().collect::<Vec<_>>();
+        let (seconds, rest) = match fields.len() {
+            5 => (vec![0].into_iter().collect(), &fields[..]),
+            6 => (SECOND.parse(fields[0])?, &fields[1..]),
+            n => {
+                return Err(CronError::new(format!(
+                    "expected 5 or 6 fields but found {}",
+                    n
+                )))
+            }
+        };
+        let restricted = |field: &str| !(field.starts_with('*') || field.starts_with('?'));
+        let days_of_week = DAY_OF_WEEK
+            .parse(rest[4])?
+            .into_iter()
+            .map(|day| day % 7)
+            .collect();
+        Ok(CronSchedule {
+            expression: if trimmed.starts_with('@') {
+                trimmed.to_string()
+            } else {
+                fields.join(" ")
+            },
+            seconds,
+            minutes: MINUTE.parse(rest[0])?,
+            hours: HOUR.parse(rest[1])?,
+            days_of_month: DAY_OF_MONTH.parse(rest[2])?,
+            months: MONTH.parse(rest[3])?,
+            days_of_week,
+            day_of_month_restricted: restricted(rest[2]),
+            day_of_week_restricted: restricted(rest[4]),
+        })
+    }
+}
+
+/// A text input for a cron expression. Use `CronSchedule::upcoming` on the parsed value to
+/// preview the next fire times.
+pub type Cron = crate::inputs::Scalar<CronSchedule, CronError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> chrono::NaiveDateTime {
+        chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn round_trips() {
+        for expression in ["*/15 9-17 * * MON-FRI", "30 0 0 1 1 *", "@daily"].iter() {
+            let schedule = expression.parse::<CronSchedule>().unwrap();
+            assert_eq!(&schedule.to_string(), expression);
+            assert_eq!(
+                schedule.to_string().parse::<CronSchedule>().unwrap(),
+                schedule
+            );
+        }
+        let schedule = "0 0 * * 7".parse::<CronSchedule>().unwrap();
+        assert_eq!(schedule.get_days_of_week(), &vec![0].into_iter().collect());
+    }
+
+    #[test]
+    fn rejects() {
+        for expression in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "5-1 * * * *",
+            "*/0 * * * *",
+            "1,,2 * * * *",
+            "x * * * *",
+            "@fortnightly",
+        ]
+        .iter()
+        {
+            assert!(
+                expression.parse::<CronSchedule>().is_err(),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn next_after() {
+        let schedule = "0 0 29 2 *".parse::<CronSchedule>().unwrap();
+        assert_eq!(
+            schedule.next_after(at("2021-03-01 00:00:00")),
+            Some(at("2024-02-29 00:00:00"))
+        );
+        let schedule = "*/15 9-17 * * MON-FRI".parse::<CronSchedule>().unwrap();
+        assert_eq!(
+            schedule.upcoming(at("2021-10-08 17:40:00"), 2),
+            vec![at("2021-10-08 17:45:00"), at("2021-10-11 09:00:00")]
+        );
+    }
+
+    #[test]
+    fn end_of_calendar() {
+        let schedule = "0 0 1 1 *".parse::<CronSchedule>().unwrap();
+        let last = chrono::naive::MAX_DATE.and_hms(23, 59, 59);
+        assert_eq!(schedule.next_after(last), None);
+        assert_eq!(schedule.next_after(last - chrono::Duration::days(30)), None);
+    }
+}
@@ -0,0 +1,468 @@
+use rust_decimal::prelude::ToPrimitive;
+use std::{error, fmt};
+
+#[derive(Debug)]
+pub struct DurationError {
+    input: String,
+    reason: String,
+}
+
+impl DurationError {
+    pub fn new(input: String, reason: String) -> DurationError {
+        DurationError { input, reason }
+    }
+}
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a valid duration: {}", self.input, self.reason)
+    }
+}
+
+impl error::Error for DurationError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationFormat {
+    /// Numbers with units, eg `1h 30m`, `90 min` or `2d`
+    Human,
+    /// ISO-8601 durations without years or months, eg `PT1H30M` or `P2D`
+    Iso8601,
+}
+
+const UNITS: &[(&[&str], i64)] = &[
+    (
+        &["w", "wk", "wks", "week", "weeks"],
+        7 * 24 * 60 * 60 * 1000,
+    ),
+    (&["d", "day", "days"], 24 * 60 * 60 * 1000),
+    (&["h", "hr", "hrs", "hour", "hours"], 60 * 60 * 1000),
+    (&["m", "min", "mins", "minute", "minutes"], 60 * 1000),
+    (&["s", "sec", "secs", "second", "seconds"], 1000),
+    (&["ms", "millis", "millisecond", "milliseconds"], 1),
+];
+
+fn amount_millis(input: &str, amount: &str, unit_millis: i64) -> Result<i64, DurationError> {
+    let amount = amount.parse::<rust_decimal::Decimal>().map_err(|_| {
+        DurationError::new(input.to_string(), format!("{} is not a number", amount))
+    })?;
+    if amount.is_sign_negative() {
+        return Err(DurationError::new(
+            input.to_string(),
+            "durations can't be negative".to_string(),
+        ));
+    }
+    let too_long = || DurationError::new(input.to_string(), "duration is too long".to_string());
+    let millis = amount
+        .checked_mul(rust_decimal::Decimal::from(unit_millis))
+        .ok_or_else(too_long)?;
+    if !millis.fract().is_zero() {
+        return Err(DurationError::new(
+            input.to_string(),
+            "durations can't be more precise than a millisecond".to_string(),
+        ));
+    }
+    millis.to_i64().ok_or_else(too_long)
+}
+
+fn parse_human(input: &str) -> Result<chrono::Duration, DurationError> {
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        return Err(DurationError::new(
+            input.to_string(),
+            "no duration was entered".to_string(),
+        ));
+    }
+    let mut total = 0i64;
+    while !rest.is_empty() {
+        let amount_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let (amount, after) = rest.split_at(amount_len);
+        if amount.is_empty() {
+            return Err(DurationError::new(
+                input.to_string(),
+                format!("expected a number at {}", rest),
+            ));
+        }
+        let after = after.trim_start();
+        let unit_len = after
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+        if unit.is_empty() {
+            return Err(DurationError::new(
+                input.to_string(),
+                format!("{} is missing a unit such as h, m or s", amount),
+            ));
+        }
+        let lowercase = unit.to_ascii_lowercase();
+        let unit_millis = UNITS
+            .iter()
+            .find(|(names, _)| names.contains(&lowercase.as_str()))
+            .map(|(_, millis)| *millis)
+            .ok_or_else(|| {
+                DurationError::new(input.to_string(), format!("{} is not a known unit", unit))
+            })?;
+        total = total
+            .checked_add(amount_millis(input, amount, unit_millis)?)
+            .ok_or_else(|| {
+                DurationError::new(input.to_string(), "duration is too long".to_string())
+            })?;
+        rest = after.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+    Ok(chrono::Duration::milliseconds(total))
+}
+
+fn parse_iso8601(input: &str) -> Result<chrono::Duration, DurationError> {
+    let trimmed = input.trim().to_ascii_uppercase();
+    let body = trimmed.strip_prefix('P').ok_or_else(|| {
+        DurationError::new(
+            input.to_string(),
+            "ISO-8601 durations start with P".to_string(),
+        )
+    })?;
+    let (date_part, time_part) = match body.find('T') {
+        Some(idx) => (&body[..idx], Some(&body[idx + 1..])),
+        None => (body, None),
+    };
+    if date_part.is_empty() && time_part.unwrap_or("").is_empty() {
+        return Err(DurationError::new(
+            input.to_string(),
+            "no duration was entered".to_string(),
+        ));
+    }
+    let mut total = 0i64;
+    let mut parts = vec![(date_part, false)];
+    if let Some(time_part) = time_part {
+        parts.push((time_part, true));
+    }
+    for (part, is_time) in parts {
+        let mut amount_start = 0;
+        for (idx, c) in part.char_indices() {
+            if c.is_ascii_digit() || c == '.' || c == ',' {
+                continue;
+            }
+            let amount = part[amount_start..idx].replace(',', ".");
+            let unit_millis = match (c, is_time) {
+                ('W', false) => 7 * 24 * 60 * 60 * 1000,
+                ('D', false) => 24 * 60 * 60 * 1000,
+                ('H', true) => 60 * 60 * 1000,
+                ('M', true) => 60 * 1000,
+                ('S', true) => 1000,
+                ('Y', false) | ('M', false) => {
+                    return Err(DurationError::new(
+                        input.to_string(),
+                        "years and months don't have a fixed length".to_string(),
+                    ))
+                }
+                _ => {
+                    return Err(DurationError::new(
+                        input.to_string(),
+                        format!("{} is not a valid designator here", c),
+                    ))
+                }
+            };
+            if amount.is_empty() {
+                return Err(DurationError::new(
+                    input.to_string(),
+                    format!("{} is missing a number", c),
+                ));
+            }
+            total = total
+                .checked_add(amount_millis(input, &amount, unit_millis)?)
+                .ok_or_else(|| {
+                    DurationError::new(input.to_string(), "duration is too long".to_string())
+                })?;
+            amount_start = idx + c.len_utf8();
+        }
+        if amount_start != part.len() {
+            return Err(DurationError::new(
+                input.to_string(),
+                format!("{} is missing a designator", &part[amount_start..]),
+            ));
+        }
+    }
+    Ok(chrono::Duration::milliseconds(total))
+}
+
+impl DurationFormat {
+    fn looks_like(&self, input: &str) -> bool {
+        let input = input.trim_start();
+        match self {
+            DurationFormat::Human => input.starts_with(|c: char| c.is_ascii_digit()),
+            DurationFormat::Iso8601 => input.starts_with(['P', 'p'].as_ref()),
+        }
+    }
+    pub fn parse(&self, input: &str) -> Result<chrono::Duration, DurationError> {
+        // what `format` shows for a negative duration
+        if input.trim_start().starts_with('-') {
+            return Err(DurationError::new(
+                input.to_string(),
+                "durations can't be negative".to_string(),
+            ));
+        }
+        match self {
+            DurationFormat::Human => parse_human(input),
+            DurationFormat::Iso8601 => parse_iso8601(input),
+        }
+    }
+    /// Negative durations get a leading `-`, eg `-1h 30m` or `-PT1H30M`, so they show as what
+    /// is stored even though `parse` rejects them.
+    pub fn format(&self, data: &chrono::Duration) -> String {
+        let millis = data.num_milliseconds();
+        let sign = if millis < 0 { "-" } else { "" };
+        let mut millis = millis.abs();
+        let mut parts = Vec::new();
+        let formatted = match self {
+            DurationFormat::Human => {
+                for (names, unit_millis) in UNITS.iter().skip(1) {
+                    if millis >= *unit_millis {
+                        parts.push(format!("{}{}", millis / unit_millis, names[0]));
+                        millis %= unit_millis;
+                    }
+                }
+                if parts.is_empty() {
+                    "0s".to_string()
+                } else {
+                    parts.join(" ")
+                }
+            }
+            DurationFormat::Iso8601 => {
+                let days = millis / (24 * 60 * 60 * 1000);
+                millis %= 24 * 60 * 60 * 1000;
+                let hours = millis / (60 * 60 * 1000);
+                millis %= 60 * 60 * 1000;
+                let minutes = millis / (60 * 1000);
+                millis %= 60 * 1000;
+                let mut formatted = "P".to_string();
+                if days > 0 {
+                    formatted.push_str(&format!("{}D", days));
+                }
+                if hours > 0 || minutes > 0 || millis > 0 || days == 0 {
+                    formatted.push('T');
+                }
+                if hours > 0 {
+                    formatted.push_str(&format!("{}H", hours));
+                }
+                if minutes > 0 {
+                    formatted.push_str(&format!("{}M", minutes));
+                }
+                if millis % 1000 > 0 {
+                    formatted.push_str(&format!("{}.{:03}S", millis / 1000, millis % 1000));
+                } else if millis > 0 || formatted.ends_with('T') {
+                    formatted.push_str(&format!("{}S", millis / 1000));
+                }
+                formatted
+            }
+        };
+        format!("{}{}", sign, formatted)
+    }
+}
+
+/// A duration typed in any of the accepted formats. `DurationMsg::Normalise` rewrites the raw
+/// input in the display format once it parses (eg on blur), so `90 min` becomes `1h 30m`.
+pub struct Duration {
+    input: String,
+    formats: Vec<DurationFormat>,
+    display: DurationFormat,
+    min: Option<chrono::Duration>,
+    max: Option<chrono::Duration>,
+    validations: crate::Validations<chrono::Duration>,
+}
+
+pub enum DurationMsg {
+    Input(String),
+    Normalise,
+}
+
+impl Duration {
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+    pub fn new(
+        data: chrono::Duration,
+        formats: Vec<DurationFormat>,
+        display: DurationFormat,
+        min: Option<chrono::Duration>,
+        max: Option<chrono::Duration>,
+        validations: crate::Validations<chrono::Duration>,
+    ) -> Duration {
+        Duration {
+            input: display.format(&data),
+            formats,
+            display,
+            min,
+            max,
+            validations,
+        }
+    }
+    pub fn get_min(&self) -> Option<chrono::Duration> {
+        self.min
+    }
+    pub fn get_max(&self) -> Option<chrono::Duration> {
+        self.max
+    }
+}
+
+impl Default for Duration {
+    fn default() -> Duration {
+        Duration::new(
+            chrono::Duration::zero(),
+            vec![DurationFormat::Human, DurationFormat::Iso8601],
+            DurationFormat::Human,
+            None,
+            None,
+            crate::Validations::new(),
+        )
+    }
+}
+
+impl crate::SetInput for Duration {
+    fn set(&mut self, data: chrono::Duration) {
+        self.input = self.display.format(&data);
+    }
+}
+
+impl crate::UserInput for Duration {
+    type Output = chrono::Duration;
+    type Input = DurationMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            DurationMsg::Input(input) => self.input = input,
+            DurationMsg::Normalise => {
+                if let Ok(parsed) = self.parse() {
+                    self.input = self.display.format(&parsed);
+                }
+            }
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let mut errors = Vec::new();
+        let mut parsed = None;
+        for format in &self.formats {
+            match format.parse(&self.input) {
+                Ok(duration) => {
+                    parsed = Some(duration);
+                    break;
+                }
+                Err(e) => errors.push((format, e)),
+            }
+        }
+        let parsed = match parsed {
+            Some(parsed) => parsed,
+            None => {
+                // report the error from the format the user seems to have been typing
+                let idx = errors
+                    .iter()
+                    .position(|(format, _)| format.looks_like(&self.input))
+                    .unwrap_or(0);
+                return Err(if errors.is_empty() {
+                    DurationError::new(
+                        self.input.clone(),
+                        "no duration formats are accepted".to_string(),
+                    )
+                } else {
+                    errors.swap_remove(idx).1
+                }
+                .into());
+            }
+        };
+        crate::check_bounds(
+            "Duration",
+            ("at least", "at most"),
+            &parsed,
+            (self.min.as_ref(), self.max.as_ref()),
+            |duration| self.display.format(duration),
+        )
+        .map_err(|e| crate::Error::Validation(vec![e].into()))?;
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutes(minutes: i64) -> chrono::Duration {
+        chrono::Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn parses_human_durations() {
+        let human = DurationFormat::Human;
+        assert_eq!(human.parse("1h 30m").unwrap(), minutes(90));
+        assert_eq!(human.parse("90 min").unwrap(), minutes(90));
+        assert_eq!(human.parse("1.5 hours").unwrap(), minutes(90));
+        assert_eq!(human.parse("2d, 1h").unwrap(), minutes(49 * 60));
+        assert_eq!(
+            human.parse("250ms").unwrap(),
+            chrono::Duration::milliseconds(250)
+        );
+    }
+
+    #[test]
+    fn parses_iso8601_durations() {
+        let iso = DurationFormat::Iso8601;
+        assert_eq!(iso.parse("PT1H30M").unwrap(), minutes(90));
+        assert_eq!(iso.parse("p2d").unwrap(), minutes(2 * 24 * 60));
+        assert_eq!(iso.parse("P1W").unwrap(), minutes(7 * 24 * 60));
+        assert_eq!(
+            iso.parse("PT0,5S").unwrap(),
+            chrono::Duration::milliseconds(500)
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for format in [DurationFormat::Human, DurationFormat::Iso8601].iter() {
+            for duration in [
+                chrono::Duration::zero(),
+                minutes(90),
+                minutes(3 * 24 * 60 + 5),
+                chrono::Duration::milliseconds(61_001),
+            ]
+            .iter()
+            {
+                let formatted = format.format(duration);
+                assert_eq!(
+                    format.parse(&formatted).unwrap(),
+                    *duration,
+                    "{}",
+                    formatted
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn formats_negative_durations_with_a_sign() {
+        assert_eq!(DurationFormat::Human.format(&minutes(-90)), "-1h 30m");
+        assert_eq!(DurationFormat::Iso8601.format(&minutes(-90)), "-PT1H30M");
+        assert!(DurationFormat::Human.parse("-1h 30m").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        let human = DurationFormat::Human;
+        for input in ["", "h", "5", "5 parsecs", "0.5ms", "1h -5m"].iter() {
+            assert!(human.parse(input).is_err(), "{}", input);
+        }
+        let iso = DurationFormat::Iso8601;
+        for input in ["", "P", "1H", "P1M", "P1Y", "PT5", "PT1D", "P1H"].iter() {
+            assert!(iso.parse(input).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn rejects_overflowing_durations_without_panicking() {
+        let error = DurationFormat::Human
+            .parse("99999999999999999999999999w")
+            .unwrap_err();
+        assert!(error.to_string().contains("duration is too long"));
+        assert!(DurationFormat::Human.parse("9999999999999999999d").is_err());
+        assert!(DurationFormat::Iso8601
+            .parse("P99999999999999999999999999W")
+            .is_err());
+    }
+}
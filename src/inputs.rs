@@ -1,5 +1,6 @@
 use chrono::Datelike;
 use resolution::DateResolution as DateResolutionTrait;
+use resolution::TimeResource as TimeResourceTrait;
 use std::{cmp, collections, convert, error, fmt, marker, num};
 
 #[derive(Debug)]
@@ -44,6 +45,22 @@ impl<E: error::Error + Sync + Send + 'static, O: fmt::Display + Ord + std::str::
             o: marker::PhantomData,
         }
     }
+    pub fn get_options(&self) -> &collections::BTreeSet<O> {
+        &self.options
+    }
+    pub fn set(&mut self, data: O) {
+        self.input = data.to_string();
+    }
+}
+
+impl<
+        E: error::Error + Sync + Send + 'static,
+        O: fmt::Display + Ord + std::str::FromStr<Err = E>,
+    > crate::SetInput for Select<E, O>
+{
+    fn set(&mut self, data: O) {
+        Select::set(self, data);
+    }
 }
 
 impl<E: error::Error + Sync + Send + 'static, O: fmt::Display + Ord + std::str::FromStr<Err = E>> crate::UserInput
@@ -123,6 +140,8 @@ where
 {
     input: String,
     validations: crate::Validations<O>,
+    format: Option<crate::number_format::NumberFormat>,
+    constraints: Option<crate::numeric::NumberConstraints<O>>,
     o: marker::PhantomData<O>,
 }
 
@@ -138,9 +157,67 @@ where
         Scalar {
             input: data.to_string(),
             validations,
+            format: None,
+            constraints: None,
+            o: marker::PhantomData,
+        }
+    }
+    /// An input with nothing entered yet, for when there is no sensible initial value.
+    pub fn new_blank(validations: crate::Validations<O>) -> Scalar<O, E> {
+        Scalar {
+            input: String::new(),
+            validations,
+            format: None,
+            constraints: None,
             o: marker::PhantomData,
         }
     }
+    /// For numeric outputs, accepts and displays numbers in `format` (eg `1.234,56`) rather
+    /// than only the plain `FromStr`/`Display` representation.
+    pub fn with_format(
+        data: &O,
+        format: crate::number_format::NumberFormat,
+        validations: crate::Validations<O>,
+    ) -> Scalar<O, E> {
+        Scalar {
+            input: format.format(&data.to_string()),
+            validations,
+            format: Some(format),
+            constraints: None,
+            o: marker::PhantomData,
+        }
+    }
+    pub fn get_format(&self) -> Option<&crate::number_format::NumberFormat> {
+        self.format.as_ref()
+    }
+    /// Changes the format, rewriting the raw input in the new format if it parsed in the old one.
+    pub fn set_format(&mut self, format: Option<crate::number_format::NumberFormat>) {
+        let current = self.parse_input().ok();
+        self.format = format;
+        if let Some(current) = current {
+            self.set(current);
+        }
+    }
+    pub fn get_constraints(&self) -> Option<&crate::numeric::NumberConstraints<O>> {
+        self.constraints.as_ref()
+    }
+    /// Sets declarative min/max/step constraints, which are checked on `parse` before the
+    /// validations and can be read back by renderers (eg for an HTML number input).
+    pub fn set_constraints(&mut self, constraints: Option<crate::numeric::NumberConstraints<O>>) {
+        self.constraints = constraints;
+    }
+    fn parse_input(&self) -> crate::Result<O> {
+        Ok(match &self.format {
+            Some(format) => format.normalise(&self.input)?.parse()?,
+            None => self.input.parse()?,
+        })
+    }
+    pub fn set(&mut self, data: O) {
+        self.input = match &self.format {
+            Some(format) => format.format(&data.to_string()),
+            None => data.to_string(),
+        };
+    }
 }
 
 impl<O, E> Default for Scalar<O, E>
@@ -164,12 +241,27 @@ where
         self.input = input;
     }
     fn parse(&self) -> crate::Result<Self::Output> {
-        let parsed = self.input.parse()?;
+        let parsed = self.parse_input()?;
+        if let Some(constraints) = &self.constraints {
+            constraints
+                .check(&parsed)
+                .map_err(|e| crate::Error::Validation(vec![e].into()))?;
+        }
         self.validations.validate(&parsed)?;
         Ok(parsed)
     }
 }
 
+impl<O, E> crate::SetInput for Scalar<O, E>
+where
+    O: std::str::FromStr<Err = E> + fmt::Display,
+    E: error::Error + Sync + Send + 'static,
+{
+    fn set(&mut self, data: O) {
+        Scalar::set(self, data);
+    }
+}
+
 pub type Integer<I> = Scalar<I, num::ParseIntError>;
 pub type Decimal = Scalar<rust_decimal::Decimal, rust_decimal::Error>;
 pub type Text = Scalar<String, convert::Infallible>;
@@ -195,6 +287,19 @@ impl NaiveDate {
             validations,
         }
     }
+    /// Whether `data` would pass this input's validations, eg to disable days in a calendar.
+    pub fn is_valid(&self, data: &chrono::NaiveDate) -> bool {
+        self.validations.validate(data).is_ok()
+    }
+    pub fn set(&mut self, data: chrono::NaiveDate) {
+        self.input = data.format(self.format).to_string();
+    }
+}
+
+impl crate::SetInput for NaiveDate {
+    fn set(&mut self, data: chrono::NaiveDate) {
+        NaiveDate::set(self, data);
+    }
 }
 
 impl Default for NaiveDate {
@@ -220,6 +325,80 @@ impl crate::UserInput for NaiveDate {
     }
 }
 
+/// A multi-select over the days of the week. The output is ordered from Monday.
+pub struct Weekdays {
+    selected: [bool; 7],
+    validations: crate::Validations<Vec<chrono::Weekday>>,
+}
+
+pub enum WeekdaysMsg {
+    Toggle(chrono::Weekday),
+    Set(chrono::Weekday, bool),
+}
+
+impl Weekdays {
+    pub fn new(
+        data: &[chrono::Weekday],
+        validations: crate::Validations<Vec<chrono::Weekday>>,
+    ) -> Weekdays {
+        let mut weekdays = Weekdays {
+            selected: [false; 7],
+            validations,
+        };
+        weekdays.set(data.to_vec());
+        weekdays
+    }
+    pub fn is_selected(&self, day: chrono::Weekday) -> bool {
+        self.selected[day.num_days_from_monday() as usize]
+    }
+    pub fn set(&mut self, data: Vec<chrono::Weekday>) {
+        self.selected = [false; 7];
+        for day in data {
+            self.selected[day.num_days_from_monday() as usize] = true;
+        }
+    }
+}
+
+impl Default for Weekdays {
+    fn default() -> Weekdays {
+        Weekdays::new(&[], crate::Validations::new())
+    }
+}
+
+impl crate::SetInput for Weekdays {
+    fn set(&mut self, data: Vec<chrono::Weekday>) {
+        Weekdays::set(self, data);
+    }
+}
+
+impl crate::UserInput for Weekdays {
+    type Output = Vec<chrono::Weekday>;
+    type Input = WeekdaysMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            WeekdaysMsg::Toggle(day) => {
+                let idx = day.num_days_from_monday() as usize;
+                self.selected[idx] = !self.selected[idx];
+            }
+            WeekdaysMsg::Set(day, selected) => {
+                self.selected[day.num_days_from_monday() as usize] = selected
+            }
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let mut day = chrono::Weekday::Mon;
+        let mut parsed = Vec::new();
+        for selected in &self.selected {
+            if *selected {
+                parsed.push(day);
+            }
+            day = day.succ();
+        }
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
 pub struct Date {
     input: String,
     format: &'static str,
@@ -251,6 +430,19 @@ impl Date {
             validations,
         }
     }
+    pub fn set(&mut self, data: resolution::Date) {
+        self.input = data.start().format(self.format).to_string();
+    }
+    /// Whether `data` would pass this input's validations, eg to disable days in a calendar.
+    pub fn is_valid(&self, data: &resolution::Date) -> bool {
+        self.validations.validate(data).is_ok()
+    }
+}
+
+impl crate::SetInput for Date {
+    fn set(&mut self, data: resolution::Date) {
+        Date::set(self, data);
+    }
 }
 
 impl crate::UserInput for Date {
@@ -290,6 +482,15 @@ impl Year {
             validations,
         }
     }
+    pub fn set(&mut self, data: resolution::Year) {
+        self.input = data.to_string();
+    }
+}
+
+impl crate::SetInput for Year {
+    fn set(&mut self, data: resolution::Year) {
+        Year::set(self, data);
+    }
 }
 
 impl crate::UserInput for Year {
@@ -326,6 +527,9 @@ impl RelativeMonth {
             validations,
         }
     }
+    pub fn set(&mut self, data: u32) {
+        self.input = data.to_string();
+    }
 }
 
 impl crate::UserInput for RelativeMonth {
@@ -389,6 +593,22 @@ impl Month {
             validations,
         }
     }
+    pub fn set(&mut self, data: resolution::Month) {
+        self.year.set(data.year());
+        self.month.set(data.month_num());
+    }
+    /// Whether `data` would pass the validations of this input and its sub-inputs.
+    pub fn is_valid(&self, data: &resolution::Month) -> bool {
+        self.year.validations.validate(&data.year()).is_ok()
+            && self.month.validations.validate(&data.month_num()).is_ok()
+            && self.validations.validate(data).is_ok()
+    }
+}
+
+impl crate::SetInput for Month {
+    fn set(&mut self, data: resolution::Month) {
+        Month::set(self, data);
+    }
 }
 
 impl crate::UserInput for Month {
@@ -426,6 +646,9 @@ impl RelativeQuarter {
             validations,
         }
     }
+    pub fn set(&mut self, data: u32) {
+        self.input = data.to_string();
+    }
 }
 
 impl crate::UserInput for RelativeQuarter {
@@ -498,6 +721,26 @@ impl Quarter {
             validations,
         }
     }
+    pub fn set(&mut self, data: resolution::Quarter) {
+        self.year.set(data.year());
+        self.quarter.set(data.quarter_num());
+    }
+    /// Whether `data` would pass the validations of this input and its sub-inputs.
+    pub fn is_valid(&self, data: &resolution::Quarter) -> bool {
+        self.year.validations.validate(&data.year()).is_ok()
+            && self
+                .quarter
+                .validations
+                .validate(&data.quarter_num())
+                .is_ok()
+            && self.validations.validate(data).is_ok()
+    }
+}
+
+impl crate::SetInput for Quarter {
+    fn set(&mut self, data: resolution::Quarter) {
+        Quarter::set(self, data);
+    }
 }
 
 impl crate::UserInput for Quarter {
@@ -542,6 +785,9 @@ where
             input,
         }
     }
+    pub fn get_input(&self) -> &I {
+        &self.input
+    }
 }
 
 impl<I, R> From<I> for DateResolution<I, R>
@@ -579,6 +825,79 @@ where
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeRangePreset {
+    LastTwelveMonths,
+    YearToDate,
+    PreviousQuarter,
+    TrailingFourQuarters,
+}
+
+impl fmt::Display for TimeRangePreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeRangePreset::LastTwelveMonths => write!(f, "Last 12 months"),
+            TimeRangePreset::YearToDate => write!(f, "Year to date"),
+            TimeRangePreset::PreviousQuarter => write!(f, "Previous quarter"),
+            TimeRangePreset::TrailingFourQuarters => write!(f, "Trailing 4 quarters"),
+        }
+    }
+}
+
+fn add_months(first_of_month: chrono::NaiveDate, months: i32) -> chrono::NaiveDate {
+    let index = first_of_month.year() * 12 + first_of_month.month0() as i32 + months;
+    chrono::NaiveDate::from_ymd(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1)
+}
+
+impl TimeRangePreset {
+    pub fn all() -> [TimeRangePreset; 4] {
+        [
+            TimeRangePreset::LastTwelveMonths,
+            TimeRangePreset::YearToDate,
+            TimeRangePreset::PreviousQuarter,
+            TimeRangePreset::TrailingFourQuarters,
+        ]
+    }
+    /// The first and last days (inclusive) covered by the preset, relative to `reference`.
+    /// Only whole months and quarters before the one containing `reference` are counted,
+    /// except for `YearToDate` which runs up to and including `reference`.
+    pub fn bounds(&self, reference: chrono::NaiveDate) -> (chrono::NaiveDate, chrono::NaiveDate) {
+        let month_start = reference.with_day(1).expect("all months have a first day");
+        let quarter_start = add_months(month_start, -(month_start.month0() as i32 % 3));
+        match self {
+            TimeRangePreset::LastTwelveMonths => (add_months(month_start, -12), month_start.pred()),
+            TimeRangePreset::YearToDate => (
+                chrono::NaiveDate::from_ymd(reference.year(), 1, 1),
+                reference,
+            ),
+            TimeRangePreset::PreviousQuarter => {
+                (add_months(quarter_start, -3), quarter_start.pred())
+            }
+            TimeRangePreset::TrailingFourQuarters => {
+                (add_months(quarter_start, -12), quarter_start.pred())
+            }
+        }
+    }
+    /// The start period and length of the preset at resolution `R`. Periods only partly covered
+    /// by the preset's bounds are included.
+    pub fn period<R: resolution::DateResolution>(
+        &self,
+        reference: chrono::NaiveDate,
+    ) -> Option<(R, u32)> {
+        let (start, end) = self.bounds(reference);
+        let start = R::from_date(start);
+        let len = periods_between(start, R::from_date(end))?;
+        Some((start, len))
+    }
+    pub fn range<R: resolution::DateResolution>(
+        &self,
+        reference: chrono::NaiveDate,
+    ) -> Option<resolution::TimeRange<R>> {
+        self.period(reference)
+            .map(|(start, len)| resolution::TimeRange::new(start, len))
+    }
+}
+
 pub struct TimeRange<I, R>
 where
     R: resolution::DateResolution,
@@ -618,6 +937,17 @@ where
             range_validations,
         }
     }
+    pub fn get_date_resolution(&self) -> &DateResolution<I, R> {
+        &self.date_resolution
+    }
+    pub fn get_length(&self) -> &Integer<u32> {
+        &self.length
+    }
+    fn parse_parts(&self) -> crate::Result<(R, u32)> {
+        let start = crate::UserInput::parse(&self.date_resolution)?;
+        let len = crate::UserInput::parse(&self.length)?;
+        Ok((start, len))
+    }
 }
 
 pub enum TimeRangeMsg<I, R>
@@ -651,3 +981,628 @@ where
         Ok(range)
     }
 }
+
+/// A `TimeRange` with preset shortcuts (eg "Last 12 months") that fill in its start and length
+/// when chosen with `PresetTimeRangeMsg::Preset`. The start input must implement `SetInput` so
+/// that it can be filled in.
+pub struct PresetTimeRange<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + crate::SetInput + Default,
+{
+    range: TimeRange<I, R>,
+    presets: Vec<TimeRangePreset>,
+}
+
+impl<I, R> PresetTimeRange<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + crate::SetInput + Default,
+{
+    pub fn new(range: TimeRange<I, R>, presets: Vec<TimeRangePreset>) -> PresetTimeRange<I, R> {
+        PresetTimeRange { range, presets }
+    }
+    pub fn get_range(&self) -> &TimeRange<I, R> {
+        &self.range
+    }
+    pub fn get_presets(&self) -> &[TimeRangePreset] {
+        &self.presets
+    }
+    /// The first of the offered presets that the current start and length match, if any.
+    pub fn matching_preset(&self, reference: chrono::NaiveDate) -> Option<TimeRangePreset> {
+        let (start, len) = self.range.parse_parts().ok()?;
+        self.presets
+            .iter()
+            .copied()
+            .find(|preset| preset.period::<R>(reference) == Some((start, len)))
+    }
+    pub fn apply_preset(&mut self, preset: TimeRangePreset, reference: chrono::NaiveDate) {
+        if let Some((start, len)) = preset.period::<R>(reference) {
+            self.range.date_resolution.input.set(start);
+            self.range.length.set(len);
+        }
+    }
+}
+
+pub enum PresetTimeRangeMsg<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + crate::SetInput + Default,
+{
+    Range(TimeRangeMsg<I, R>),
+    Preset(TimeRangePreset, chrono::NaiveDate),
+}
+
+impl<I, R> crate::UserInput for PresetTimeRange<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + crate::SetInput + Default,
+{
+    type Output = resolution::TimeRange<R>;
+    type Input = PresetTimeRangeMsg<I, R>;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            PresetTimeRangeMsg::Range(input) => self.range.update(input),
+            PresetTimeRangeMsg::Preset(preset, reference) => self.apply_preset(preset, reference),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        self.range.parse()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ComparisonKind {
+    None,
+    PreviousPeriod,
+    SamePeriodLastYear,
+    Custom,
+}
+
+impl fmt::Display for ComparisonKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComparisonKind::None => write!(f, "No comparison"),
+            ComparisonKind::PreviousPeriod => write!(f, "Previous period"),
+            ComparisonKind::SamePeriodLastYear => write!(f, "Same period last year"),
+            ComparisonKind::Custom => write!(f, "Custom period"),
+        }
+    }
+}
+
+/// A `TimeRange` together with the range it should be compared against. The comparison always
+/// has the same resolution as the primary range, and is required to have the same length.
+pub struct ComparedTimeRange<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + Default,
+{
+    primary: TimeRange<I, R>,
+    kind: ComparisonKind,
+    custom: TimeRange<I, R>,
+}
+
+impl<I, R> ComparedTimeRange<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + Default,
+{
+    pub fn new(
+        primary: TimeRange<I, R>,
+        kind: ComparisonKind,
+        custom: TimeRange<I, R>,
+    ) -> ComparedTimeRange<I, R> {
+        ComparedTimeRange {
+            primary,
+            kind,
+            custom,
+        }
+    }
+    pub fn get_primary(&self) -> &TimeRange<I, R> {
+        &self.primary
+    }
+    pub fn get_kind(&self) -> ComparisonKind {
+        self.kind
+    }
+    pub fn get_custom(&self) -> &TimeRange<I, R> {
+        &self.custom
+    }
+    fn comparison(&self, start: R, len: u32) -> crate::Result<Option<resolution::TimeRange<R>>> {
+        let comparison_start = match self.kind {
+            ComparisonKind::None => return Ok(None),
+            ComparisonKind::PreviousPeriod => (0..len).fold(start, |period, _| period.pred()),
+            ComparisonKind::SamePeriodLastYear => R::from_date(same_day_last_year(start.start())),
+            ComparisonKind::Custom => {
+                let (custom_start, custom_len) = self.custom.parse_parts()?;
+                if custom_len != len {
+                    return Err(crate::Error::Validation(
+                        vec![format!(
+                            "Comparison length should be {} to match the primary range but was {}",
+                            len, custom_len
+                        )]
+                        .into(),
+                    ));
+                }
+                // still run the custom range's own validations
+                crate::UserInput::parse(&self.custom)?;
+                custom_start
+            }
+        };
+        Ok(Some(resolution::TimeRange::new(comparison_start, len)))
+    }
+}
+
+fn same_day_last_year(date: chrono::NaiveDate) -> chrono::NaiveDate {
+    // 29th Feb falls back to the 28th
+    date.with_year(date.year() - 1)
+        .unwrap_or_else(|| chrono::NaiveDate::from_ymd(date.year() - 1, date.month(), 28))
+}
+
+pub enum ComparedTimeRangeMsg<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + Default,
+{
+    Primary(TimeRangeMsg<I, R>),
+    Kind(ComparisonKind),
+    Custom(TimeRangeMsg<I, R>),
+}
+
+impl<I, R> crate::UserInput for ComparedTimeRange<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + Default,
+{
+    type Output = (resolution::TimeRange<R>, Option<resolution::TimeRange<R>>);
+    type Input = ComparedTimeRangeMsg<I, R>;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            ComparedTimeRangeMsg::Primary(input) => self.primary.update(input),
+            ComparedTimeRangeMsg::Kind(kind) => self.kind = kind,
+            ComparedTimeRangeMsg::Custom(input) => self.custom.update(input),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let primary = self.primary.parse()?;
+        let (start, len) = self.primary.parse_parts()?;
+        let comparison = self.comparison(start, len)?;
+        Ok((primary, comparison))
+    }
+}
+
+/// A `TimeRange` chosen by its first and last periods rather than a start and length. Both ends
+/// are inclusive, so a range that starts and ends in the same period has a length of one.
+pub struct TimeRangeBetween<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + Default,
+{
+    start: DateResolution<I, R>,
+    end: DateResolution<I, R>,
+    range_validations: crate::Validations<resolution::TimeRange<R>>,
+}
+
+impl<I, R> TimeRangeBetween<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + Default,
+{
+    pub fn new(
+        start_input: I,
+        end_input: I,
+        range_validations: crate::Validations<resolution::TimeRange<R>>,
+    ) -> TimeRangeBetween<I, R> {
+        TimeRangeBetween {
+            start: DateResolution::new(start_input),
+            end: DateResolution::new(end_input),
+            range_validations,
+        }
+    }
+    pub fn get_start(&self) -> &DateResolution<I, R> {
+        &self.start
+    }
+    pub fn get_end(&self) -> &DateResolution<I, R> {
+        &self.end
+    }
+    /// The number of periods covered by the current start and end, if both parse and the end
+    /// is not before the start.
+    pub fn length(&self) -> Option<u32> {
+        let start = crate::UserInput::parse(&self.start).ok()?;
+        let end = crate::UserInput::parse(&self.end).ok()?;
+        periods_between(start, end)
+    }
+}
+
+fn periods_between<R: resolution::DateResolution>(start: R, end: R) -> Option<u32> {
+    if end < start {
+        return None;
+    }
+    let mut len = 1;
+    let mut current = start;
+    while current < end {
+        current = current.succ();
+        len += 1;
+    }
+    Some(len)
+}
+
+pub enum TimeRangeBetweenMsg<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + Default,
+{
+    Start(I::Input),
+    End(I::Input),
+}
+
+impl<I, R> crate::UserInput for TimeRangeBetween<I, R>
+where
+    R: resolution::DateResolution,
+    I: crate::UserInput<Output = R> + Default,
+{
+    type Output = resolution::TimeRange<R>;
+    type Input = TimeRangeBetweenMsg<I, R>;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            TimeRangeBetweenMsg::Start(input) => self.start.update(input),
+            TimeRangeBetweenMsg::End(input) => self.end.update(input),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let start = self.start.parse()?;
+        let end = self.end.parse()?;
+        let len = periods_between(start, end).ok_or_else(|| {
+            crate::Error::Validation(
+                vec![format!("End {} should not be before start {}", end, start)].into(),
+            )
+        })?;
+        let range = resolution::TimeRange::new(start, len);
+        self.range_validations.validate(&range)?;
+        Ok(range)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ResolutionKind {
+    Day,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl fmt::Display for ResolutionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolutionKind::Day => write!(f, "Day"),
+            ResolutionKind::Month => write!(f, "Month"),
+            ResolutionKind::Quarter => write!(f, "Quarter"),
+            ResolutionKind::Year => write!(f, "Year"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AnyTimeRange {
+    Day(resolution::TimeRange<resolution::Date>),
+    Month(resolution::TimeRange<resolution::Month>),
+    Quarter(resolution::TimeRange<resolution::Quarter>),
+    Year(resolution::TimeRange<resolution::Year>),
+}
+
+impl AnyTimeRange {
+    pub fn kind(&self) -> ResolutionKind {
+        match self {
+            AnyTimeRange::Day(_) => ResolutionKind::Day,
+            AnyTimeRange::Month(_) => ResolutionKind::Month,
+            AnyTimeRange::Quarter(_) => ResolutionKind::Quarter,
+            AnyTimeRange::Year(_) => ResolutionKind::Year,
+        }
+    }
+}
+
+/// A `TimeRange` whose resolution can be switched at runtime. One sub-input is held per
+/// resolution so that each keeps its own raw input and validations; switching carries the
+/// start of the current range over to the period of the new resolution containing it.
+pub struct AnyResolution {
+    kind: ResolutionKind,
+    day: TimeRange<Date, resolution::Date>,
+    month: TimeRange<Month, resolution::Month>,
+    quarter: TimeRange<Quarter, resolution::Quarter>,
+    year: TimeRange<Year, resolution::Year>,
+}
+
+pub enum AnyResolutionMsg {
+    Resolution(ResolutionKind),
+    Day(TimeRangeMsg<Date, resolution::Date>),
+    Month(TimeRangeMsg<Month, resolution::Month>),
+    Quarter(TimeRangeMsg<Quarter, resolution::Quarter>),
+    Year(TimeRangeMsg<Year, resolution::Year>),
+}
+
+impl AnyResolution {
+    pub fn new(
+        kind: ResolutionKind,
+        day: TimeRange<Date, resolution::Date>,
+        month: TimeRange<Month, resolution::Month>,
+        quarter: TimeRange<Quarter, resolution::Quarter>,
+        year: TimeRange<Year, resolution::Year>,
+    ) -> AnyResolution {
+        AnyResolution {
+            kind,
+            day,
+            month,
+            quarter,
+            year,
+        }
+    }
+    pub fn get_kind(&self) -> ResolutionKind {
+        self.kind
+    }
+    pub fn get_day(&self) -> &TimeRange<Date, resolution::Date> {
+        &self.day
+    }
+    pub fn get_month(&self) -> &TimeRange<Month, resolution::Month> {
+        &self.month
+    }
+    pub fn get_quarter(&self) -> &TimeRange<Quarter, resolution::Quarter> {
+        &self.quarter
+    }
+    pub fn get_year(&self) -> &TimeRange<Year, resolution::Year> {
+        &self.year
+    }
+    fn current_start(&self) -> Option<chrono::NaiveDate> {
+        match self.kind {
+            ResolutionKind::Day => crate::UserInput::parse(&self.day.date_resolution)
+                .ok()
+                .map(|d| d.start()),
+            ResolutionKind::Month => crate::UserInput::parse(&self.month.date_resolution)
+                .ok()
+                .map(|d| d.start()),
+            ResolutionKind::Quarter => crate::UserInput::parse(&self.quarter.date_resolution)
+                .ok()
+                .map(|d| d.start()),
+            ResolutionKind::Year => crate::UserInput::parse(&self.year.date_resolution)
+                .ok()
+                .map(|d| d.start()),
+        }
+    }
+    fn set_kind(&mut self, kind: ResolutionKind) {
+        if kind == self.kind {
+            return;
+        }
+        // if the current start doesn't parse there is nothing sensible to carry over,
+        // so the new resolution keeps whatever it last held
+        if let Some(start) = self.current_start() {
+            match kind {
+                ResolutionKind::Day => self.day.date_resolution.input.set(start.into()),
+                ResolutionKind::Month => self
+                    .month
+                    .date_resolution
+                    .input
+                    .set(resolution::Month::from_date(start)),
+                ResolutionKind::Quarter => self
+                    .quarter
+                    .date_resolution
+                    .input
+                    .set(resolution::Quarter::from_date(start)),
+                ResolutionKind::Year => self
+                    .year
+                    .date_resolution
+                    .input
+                    .set(resolution::Year::new(start.year())),
+            }
+        }
+        self.kind = kind;
+    }
+}
+
+impl crate::UserInput for AnyResolution {
+    type Output = AnyTimeRange;
+    type Input = AnyResolutionMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            AnyResolutionMsg::Resolution(kind) => self.set_kind(kind),
+            AnyResolutionMsg::Day(msg) => self.day.update(msg),
+            AnyResolutionMsg::Month(msg) => self.month.update(msg),
+            AnyResolutionMsg::Quarter(msg) => self.quarter.update(msg),
+            AnyResolutionMsg::Year(msg) => self.year.update(msg),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        Ok(match self.kind {
+            ResolutionKind::Day => AnyTimeRange::Day(self.day.parse()?),
+            ResolutionKind::Month => AnyTimeRange::Month(self.month.parse()?),
+            ResolutionKind::Quarter => AnyTimeRange::Quarter(self.quarter.parse()?),
+            ResolutionKind::Year => AnyTimeRange::Year(self.year.parse()?),
+        })
+    }
+}
+
+/// Values that can be moved up or down by a step, for use with `Stepper`.
+pub trait Step: Sized + PartialOrd {
+    type Size;
+    fn step_up(&self, size: &Self::Size) -> Option<Self>;
+    fn step_down(&self, size: &Self::Size) -> Option<Self>;
+}
+
+macro_rules! impl_step_checked {
+    ($($t:ty),*) => {
+        $(
+            impl Step for $t {
+                type Size = $t;
+                fn step_up(&self, size: &$t) -> Option<$t> {
+                    self.checked_add(*size)
+                }
+                fn step_down(&self, size: &$t) -> Option<$t> {
+                    self.checked_sub(*size)
+                }
+            }
+        )*
+    };
+}
+
+impl_step_checked!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    rust_decimal::Decimal
+);
+
+// periods step by a number of periods, so December steps up into January of the next year
+macro_rules! impl_step_periods {
+    ($($t:ty),*) => {
+        $(
+            impl Step for $t {
+                type Size = u32;
+                fn step_up(&self, size: &u32) -> Option<$t> {
+                    Some((0..*size).fold(*self, |period, _| period.succ()))
+                }
+                fn step_down(&self, size: &u32) -> Option<$t> {
+                    Some((0..*size).fold(*self, |period, _| period.pred()))
+                }
+            }
+        )*
+    };
+}
+
+impl_step_periods!(
+    resolution::Date,
+    resolution::Month,
+    resolution::Quarter,
+    resolution::Year
+);
+
+impl Step for chrono::NaiveDate {
+    type Size = u32;
+    fn step_up(&self, size: &u32) -> Option<chrono::NaiveDate> {
+        self.checked_add_signed(chrono::Duration::days(i64::from(*size)))
+    }
+    fn step_down(&self, size: &u32) -> Option<chrono::NaiveDate> {
+        self.checked_sub_signed(chrono::Duration::days(i64::from(*size)))
+    }
+}
+
+/// Wraps an input to add increment and decrement messages, eg for spin buttons or arrow keys.
+/// Stepping is only possible when the current input parses. A step that would leave the bounds
+/// stops at the nearest bound instead, so a value that is out of bounds can be stepped back in.
+/// The bounds are also enforced on `parse`.
+pub struct Stepper<I>
+where
+    I: crate::SetInput,
+    I::Output: Step,
+{
+    input: I,
+    step: <I::Output as Step>::Size,
+    min: Option<I::Output>,
+    max: Option<I::Output>,
+}
+
+pub enum StepperMsg<I>
+where
+    I: crate::UserInput,
+{
+    Input(I::Input),
+    Increment,
+    Decrement,
+}
+
+impl<I> Stepper<I>
+where
+    I: crate::SetInput,
+    I::Output: Step + Clone + fmt::Display,
+{
+    pub fn new(
+        input: I,
+        step: <I::Output as Step>::Size,
+        min: Option<I::Output>,
+        max: Option<I::Output>,
+    ) -> Stepper<I> {
+        Stepper {
+            input,
+            step,
+            min,
+            max,
+        }
+    }
+    pub fn get_input(&self) -> &I {
+        &self.input
+    }
+    pub fn get_step(&self) -> &<I::Output as Step>::Size {
+        &self.step
+    }
+    pub fn get_min(&self) -> Option<&I::Output> {
+        self.min.as_ref()
+    }
+    pub fn get_max(&self) -> Option<&I::Output> {
+        self.max.as_ref()
+    }
+    fn clamped(&self, value: I::Output) -> I::Output {
+        match (&self.min, &self.max) {
+            (Some(min), _) if &value < min => min.clone(),
+            (_, Some(max)) if &value > max => max.clone(),
+            _ => value,
+        }
+    }
+    // a step that overflows the type stops at the bound too, if there is one
+    fn incremented(&self) -> Option<I::Output> {
+        let current = self.input.parse().ok()?;
+        let stepped = current.step_up(&self.step).or_else(|| self.max.clone())?;
+        Some(self.clamped(stepped)).filter(|value| value > &current)
+    }
+    fn decremented(&self) -> Option<I::Output> {
+        let current = self.input.parse().ok()?;
+        let stepped = current.step_down(&self.step).or_else(|| self.min.clone())?;
+        Some(self.clamped(stepped)).filter(|value| value < &current)
+    }
+    pub fn can_increment(&self) -> bool {
+        self.incremented().is_some()
+    }
+    pub fn can_decrement(&self) -> bool {
+        self.decremented().is_some()
+    }
+}
+
+impl<I> crate::UserInput for Stepper<I>
+where
+    I: crate::SetInput,
+    I::Output: Step + Clone + fmt::Display,
+{
+    type Output = I::Output;
+    type Input = StepperMsg<I>;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            StepperMsg::Input(input) => self.input.update(input),
+            StepperMsg::Increment => {
+                if let Some(value) = self.incremented() {
+                    self.input.set(value);
+                }
+            }
+            StepperMsg::Decrement => {
+                if let Some(value) = self.decremented() {
+                    self.input.set(value);
+                }
+            }
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = self.input.parse()?;
+        crate::check_bounds(
+            "Value",
+            ("at least", "at most"),
+            &parsed,
+            (self.min.as_ref(), self.max.as_ref()),
+            ToString::to_string,
+        )
+        .map_err(|e| crate::Error::Validation(vec![e].into()))?;
+        Ok(parsed)
+    }
+}
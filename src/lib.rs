@@ -1,6 +1,23 @@
 use std::{collections, error, fmt, result};
 
+pub mod address;
+pub mod business_days;
+pub mod calendar;
+pub mod coordinate;
+pub mod country;
+pub mod cron;
+pub mod duration;
 pub mod inputs;
+pub mod money;
+pub mod network;
+pub mod number_format;
+pub mod numeric;
+pub mod password;
+pub mod percent;
+pub mod phone;
+pub mod quantity;
+pub mod recurrence;
+pub mod text_area;
 
 // it doesn't make sense to ever have both kinds of errors
 // as it should be impossible for us to validate if we couldn't
@@ -74,6 +91,38 @@ impl std::fmt::Display for ValidationErrors {
 
 pub type ValidationResult = result::Result<(), String>;
 
+// the min and max checks shared by inputs with bounds, eg "Value should be at least 1 but was 0".
+// `words` describe the min and max (dates use "on or after" and "on or before") and `show`
+// formats the values for the message
+fn check_bounds<T: PartialOrd>(
+    name: &str,
+    words: (&str, &str),
+    value: &T,
+    (min, max): (Option<&T>, Option<&T>),
+    show: impl Fn(&T) -> String,
+) -> ValidationResult {
+    let (min_words, max_words) = words;
+    if let Some(min) = min.filter(|min| value < *min) {
+        return Err(format!(
+            "{} should be {} {} but was {}",
+            name,
+            min_words,
+            show(min),
+            show(value)
+        ));
+    }
+    if let Some(max) = max.filter(|max| value > *max) {
+        return Err(format!(
+            "{} should be {} {} but was {}",
+            name,
+            max_words,
+            show(max),
+            show(value)
+        ));
+    }
+    Ok(())
+}
+
 pub type FormResult<T> = result::Result<T, FormError>;
 
 #[derive(Debug)]
@@ -114,6 +163,9 @@ impl FormError {
     pub fn add_error(&mut self, field: &'static str, err: Error) {
         self.errors.insert(field, err);
     }
+    pub fn get(&self, field: &str) -> Option<&Error> {
+        self.errors.get(field)
+    }
 }
 
 impl From<ValidationErrors> for Error {
@@ -180,6 +232,12 @@ pub trait UserInput {
     fn parse(&self) -> Result<Self::Output>;
 }
 
+/// Implemented by inputs that can rewrite their raw input from a value of their output type, so
+/// that composite inputs (eg `TimeRange` presets) can fill them in on the user's behalf.
+pub trait SetInput: UserInput {
+    fn set(&mut self, data: Self::Output);
+}
+
 /// By convention, users should create a `new` style function that takes a Self::Output, some other
 /// stuff, and produces a Self.
 pub trait Form: Sized {
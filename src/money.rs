@@ -0,0 +1,247 @@
+use crate::{inputs, number_format};
+use std::{collections, error, fmt, str};
+
+#[derive(Debug)]
+pub struct CurrencyError {
+    code: String,
+}
+
+impl CurrencyError {
+    pub fn new(code: String) -> CurrencyError {
+        CurrencyError { code }
+    }
+}
+
+impl fmt::Display for CurrencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a known ISO-4217 currency code", self.code)
+    }
+}
+
+impl error::Error for CurrencyError {}
+
+struct CurrencyInfo {
+    code: &'static str,
+    minor_units: u32,
+    symbol: &'static str,
+    decimal_separator: char,
+    grouping_separator: Option<char>,
+    symbol_after: bool,
+}
+
+macro_rules! currency {
+    ($code:expr, $minor_units:expr, $symbol:expr, $decimal:expr, $grouping:expr, $after:expr) => {
+        CurrencyInfo {
+            code: $code,
+            minor_units: $minor_units,
+            symbol: $symbol,
+            decimal_separator: $decimal,
+            grouping_separator: $grouping,
+            symbol_after: $after,
+        }
+    };
+}
+
+// sorted by code. Separators follow the most common convention for each currency, as the
+// formatting of eg EUR varies between countries.
+const CURRENCIES: &[CurrencyInfo] = &[
+    currency!("AED", 2, "AED", '.', Some(','), false),
+    currency!("AUD", 2, "$", '.', Some(','), false),
+    currency!("BHD", 3, "BD", '.', Some(','), false),
+    currency!("BRL", 2, "R$", ',', Some('.'), false),
+    currency!("CAD", 2, "$", '.', Some(','), false),
+    currency!("CHF", 2, "CHF", '.', Some('\''), false),
+    currency!("CLP", 0, "$", ',', Some('.'), false),
+    currency!("CNY", 2, "¥", '.', Some(','), false),
+    currency!("CZK", 2, "Kč", ',', Some(' '), true),
+    currency!("DKK", 2, "kr.", ',', Some('.'), true),
+    currency!("EUR", 2, "€", ',', Some('.'), true),
+    currency!("GBP", 2, "£", '.', Some(','), false),
+    currency!("HKD", 2, "HK$", '.', Some(','), false),
+    currency!("IDR", 2, "Rp", ',', Some('.'), false),
+    currency!("INR", 2, "₹", '.', Some(','), false),
+    currency!("ISK", 0, "kr", ',', Some('.'), true),
+    currency!("JOD", 3, "JD", '.', Some(','), false),
+    currency!("JPY", 0, "¥", '.', Some(','), false),
+    currency!("KRW", 0, "₩", '.', Some(','), false),
+    currency!("KWD", 3, "KD", '.', Some(','), false),
+    currency!("MXN", 2, "$", '.', Some(','), false),
+    currency!("NOK", 2, "kr", ',', Some(' '), true),
+    currency!("NZD", 2, "$", '.', Some(','), false),
+    currency!("OMR", 3, "OMR", '.', Some(','), false),
+    currency!("PLN", 2, "zł", ',', Some(' '), true),
+    currency!("SAR", 2, "SAR", '.', Some(','), false),
+    currency!("SEK", 2, "kr", ',', Some(' '), true),
+    currency!("SGD", 2, "$", '.', Some(','), false),
+    currency!("TND", 3, "DT", ',', Some('.'), true),
+    currency!("USD", 2, "$", '.', Some(','), false),
+    currency!("VND", 0, "₫", ',', Some('.'), true),
+    currency!("ZAR", 2, "R", ',', Some(' '), false),
+];
+
+/// An ISO-4217 currency from the built-in table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Currency {
+    code: &'static str,
+}
+
+impl Currency {
+    pub fn all() -> collections::BTreeSet<Currency> {
+        CURRENCIES
+            .iter()
+            .map(|info| Currency { code: info.code })
+            .collect()
+    }
+    fn info(&self) -> &'static CurrencyInfo {
+        CURRENCIES
+            .iter()
+            .find(|info| info.code == self.code)
+            .expect("currencies are only created from the table")
+    }
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+    /// The number of decimal places in the currency's minor unit, eg 2 for USD (cents).
+    pub fn minor_units(&self) -> u32 {
+        self.info().minor_units
+    }
+    pub fn symbol(&self) -> &'static str {
+        self.info().symbol
+    }
+    /// The format for amounts in this currency, without the symbol.
+    pub fn number_format(&self) -> number_format::NumberFormat {
+        let info = self.info();
+        number_format::NumberFormat::new(info.decimal_separator, info.grouping_separator)
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl str::FromStr for Currency {
+    type Err = CurrencyError;
+    fn from_str(s: &str) -> Result<Currency, CurrencyError> {
+        let code = s.trim();
+        CURRENCIES
+            .iter()
+            .find(|info| info.code.eq_ignore_ascii_case(code))
+            .map(|info| Currency { code: info.code })
+            .ok_or_else(|| CurrencyError::new(s.to_string()))
+    }
+}
+
+/// An amount in a currency, with the amount scaled to the currency's minor unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MoneyValue {
+    amount: rust_decimal::Decimal,
+    currency: Currency,
+}
+
+impl MoneyValue {
+    /// Returns `None` if `amount` is more precise than the currency's minor unit.
+    pub fn new(amount: rust_decimal::Decimal, currency: Currency) -> Option<MoneyValue> {
+        if amount.normalize().scale() > currency.minor_units() {
+            return None;
+        }
+        let mut amount = amount;
+        amount.rescale(currency.minor_units());
+        Some(MoneyValue { amount, currency })
+    }
+    pub fn amount(&self) -> rust_decimal::Decimal {
+        self.amount
+    }
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+}
+
+impl fmt::Display for MoneyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let info = self.currency.info();
+        let format = self
+            .currency
+            .number_format()
+            .with_currency(info.symbol, info.symbol_after);
+        write!(f, "{}", format.format(&self.amount.to_string()))
+    }
+}
+
+/// An amount and currency. The amount is typed in the selected currency's format, and is
+/// rewritten in the new format when the currency changes.
+pub struct Money {
+    amount: inputs::Decimal,
+    currency: inputs::Select<CurrencyError, Currency>,
+    validations: crate::Validations<MoneyValue>,
+}
+
+pub enum MoneyMsg {
+    Amount(String),
+    Currency(String),
+}
+
+impl Money {
+    pub fn new(
+        data: MoneyValue,
+        currencies: collections::BTreeSet<Currency>,
+        validations: crate::Validations<MoneyValue>,
+    ) -> Money {
+        Money {
+            amount: inputs::Decimal::with_format(
+                &data.amount,
+                data.currency.number_format(),
+                crate::Validations::new(),
+            ),
+            currency: inputs::Select::new(data.currency, currencies),
+            validations,
+        }
+    }
+    pub fn get_amount(&self) -> &inputs::Decimal {
+        &self.amount
+    }
+    pub fn get_currency(&self) -> &inputs::Select<CurrencyError, Currency> {
+        &self.currency
+    }
+}
+
+impl crate::SetInput for Money {
+    fn set(&mut self, data: MoneyValue) {
+        self.currency.set(data.currency);
+        self.amount.set_format(Some(data.currency.number_format()));
+        self.amount.set(data.amount);
+    }
+}
+
+impl crate::UserInput for Money {
+    type Output = MoneyValue;
+    type Input = MoneyMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            MoneyMsg::Amount(input) => self.amount.update(input),
+            MoneyMsg::Currency(input) => {
+                self.currency.update(input);
+                if let Ok(currency) = self.currency.parse() {
+                    self.amount.set_format(Some(currency.number_format()));
+                }
+            }
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let currency = self.currency.parse()?;
+        let amount = self.amount.parse()?;
+        let parsed = MoneyValue::new(amount, currency).ok_or_else(|| {
+            crate::Error::Validation(
+                vec![format!(
+                    "{} amounts can't have more than {} decimal places",
+                    currency,
+                    currency.minor_units()
+                )]
+                .into(),
+            )
+        })?;
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
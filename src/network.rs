@@ -0,0 +1,833 @@
+use std::{error, fmt, net, str};
+
+pub type IpAddress = crate::inputs::Scalar<net::IpAddr, net::AddrParseError>;
+pub type Ipv4Address = crate::inputs::Scalar<net::Ipv4Addr, net::AddrParseError>;
+pub type Ipv6Address = crate::inputs::Scalar<net::Ipv6Addr, net::AddrParseError>;
+pub type HostnameInput = crate::inputs::Scalar<Hostname, HostnameError>;
+pub type EmailInput = crate::inputs::Scalar<Email, EmailError>;
+pub type Ipv4NetworkInput = crate::inputs::Scalar<Ipv4Network, NetworkError>;
+pub type Ipv6NetworkInput = crate::inputs::Scalar<Ipv6Network, NetworkError>;
+pub type IpNetworkInput = crate::inputs::Scalar<IpNetwork, NetworkError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostnameError {
+    Empty,
+    TooLong(usize),
+    EmptyLabel,
+    LabelTooLong(String),
+    InvalidCharacter { label: String, character: char },
+    HyphenAtEdge(String),
+    NumericTopLevel(String),
+}
+
+impl fmt::Display for HostnameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HostnameError::Empty => write!(f, "the hostname is empty"),
+            HostnameError::TooLong(len) => write!(
+                f,
+                "hostnames can be at most 253 characters but this was {}",
+                len
+            ),
+            HostnameError::EmptyLabel => {
+                write!(f, "the hostname has an empty part between two dots")
+            }
+            HostnameError::LabelTooLong(label) => write!(
+                f,
+                "'{}' is longer than the 63 characters allowed between dots",
+                label
+            ),
+            HostnameError::InvalidCharacter { label, character } => {
+                write!(f, "'{}' is not allowed in '{}'", character, label)
+            }
+            HostnameError::HyphenAtEdge(label) => {
+                write!(f, "'{}' should not start or end with a hyphen", label)
+            }
+            HostnameError::NumericTopLevel(label) => {
+                write!(
+                    f,
+                    "the last part of a hostname ('{}') should not be all digits",
+                    label
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for HostnameError {}
+
+/// A DNS hostname as per RFC 1123, stored in lowercase without any trailing dot.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hostname(String);
+
+impl Hostname {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.0.split('.')
+    }
+}
+
+impl fmt::Display for Hostname {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl str::FromStr for Hostname {
+    type Err = HostnameError;
+    fn from_str(s: &str) -> Result<Hostname, HostnameError> {
+        let s = s.trim();
+        let s = s.strip_suffix('.').unwrap_or(s);
+        if s.is_empty() {
+            return Err(HostnameError::Empty);
+        }
+        if s.len() > 253 {
+            return Err(HostnameError::TooLong(s.len()));
+        }
+        for label in s.split('.') {
+            if label.is_empty() {
+                return Err(HostnameError::EmptyLabel);
+            }
+            if label.len() > 63 {
+                return Err(HostnameError::LabelTooLong(label.to_string()));
+            }
+            if let Some(character) = label
+                .chars()
+                .find(|c| !c.is_ascii_alphanumeric() && *c != '-')
+            {
+                return Err(HostnameError::InvalidCharacter {
+                    label: label.to_string(),
+                    character,
+                });
+            }
+            if label.starts_with('-') || label.ends_with('-') {
+                return Err(HostnameError::HyphenAtEdge(label.to_string()));
+            }
+        }
+        // otherwise `1.2.3.4` would be a hostname
+        let last = s.rsplit('.').next().unwrap_or(s);
+        if last.chars().all(|c| c.is_ascii_digit()) {
+            return Err(HostnameError::NumericTopLevel(last.to_string()));
+        }
+        Ok(Hostname(s.to_ascii_lowercase()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmailError {
+    MissingAt,
+    EmptyLocalPart,
+    LocalPartTooLong(usize),
+    InvalidLocalCharacter(char),
+    MisplacedDot,
+    Domain(HostnameError),
+}
+
+impl fmt::Display for EmailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmailError::MissingAt => write!(f, "an email address needs an '@'"),
+            EmailError::EmptyLocalPart => write!(f, "there is nothing before the '@'"),
+            EmailError::LocalPartTooLong(len) => write!(
+                f,
+                "the part before the '@' can be at most 64 characters but was {}",
+                len
+            ),
+            EmailError::InvalidLocalCharacter(c) => {
+                write!(f, "'{}' is not allowed before the '@'", c)
+            }
+            EmailError::MisplacedDot => write!(
+                f,
+                "the part before the '@' should not start or end with a dot or have two in a row"
+            ),
+            EmailError::Domain(e) => write!(f, "invalid domain: {}", e),
+        }
+    }
+}
+
+impl error::Error for EmailError {}
+
+/// An email address with an unquoted local part, eg `first.last+tag@example.com`. The domain is
+/// lowercased but the local part is kept as entered, as it may be case sensitive.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Email {
+    local: String,
+    domain: Hostname,
+}
+
+impl Email {
+    pub fn local_part(&self) -> &str {
+        &self.local
+    }
+    pub fn domain(&self) -> &Hostname {
+        &self.domain
+    }
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.local, self.domain)
+    }
+}
+
+impl str::FromStr for Email {
+    type Err = EmailError;
+    fn from_str(s: &str) -> Result<Email, EmailError> {
+        let (local, domain) = s.trim().rsplit_once('@').ok_or(EmailError::MissingAt)?;
+        if local.is_empty() {
+            return Err(EmailError::EmptyLocalPart);
+        }
+        if local.len() > 64 {
+            return Err(EmailError::LocalPartTooLong(local.len()));
+        }
+        if let Some(c) = local
+            .chars()
+            .find(|c| !c.is_ascii_alphanumeric() && !".!#$%&'*+/=?^_`{|}~-".contains(*c))
+        {
+            return Err(EmailError::InvalidLocalCharacter(c));
+        }
+        if local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+            return Err(EmailError::MisplacedDot);
+        }
+        Ok(Email {
+            local: local.to_string(),
+            domain: domain.parse().map_err(EmailError::Domain)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    MissingScheme,
+    InvalidScheme(String),
+    SchemeNotAllowed {
+        scheme: String,
+        allowed: Vec<String>,
+    },
+    MissingHost,
+    CredentialsNotSupported,
+    InvalidPort(String),
+    InvalidIpv6(String),
+    Host(HostnameError),
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::MissingScheme => write!(f, "the URL should start with eg 'https://'"),
+            UrlError::InvalidScheme(scheme) => write!(f, "'{}' is not a valid scheme", scheme),
+            UrlError::SchemeNotAllowed { scheme, allowed } => write!(
+                f,
+                "'{}' URLs are not allowed, expected one of {}",
+                scheme,
+                allowed.join(", ")
+            ),
+            UrlError::MissingHost => write!(f, "the URL has no host"),
+            UrlError::CredentialsNotSupported => {
+                write!(f, "usernames and passwords in URLs are not supported")
+            }
+            UrlError::InvalidPort(port) => write!(f, "'{}' is not a valid port", port),
+            UrlError::InvalidIpv6(host) => write!(f, "'{}' is not a valid IPv6 address", host),
+            UrlError::Host(e) => write!(f, "invalid host: {}", e),
+            UrlError::InvalidCharacter(c) => write!(f, "'{}' is not allowed in a URL", c),
+        }
+    }
+}
+
+impl error::Error for UrlError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Host {
+    Domain(Hostname),
+    Ip(net::IpAddr),
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Host::Domain(hostname) => write!(f, "{}", hostname),
+            Host::Ip(net::IpAddr::V4(ip)) => write!(f, "{}", ip),
+            Host::Ip(net::IpAddr::V6(ip)) => write!(f, "[{}]", ip),
+        }
+    }
+}
+
+/// An absolute URL with a host, eg `https://example.com:8443/path?query#fragment`. The scheme
+/// and host are lowercased; the path, query and fragment are kept as entered.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Url {
+    scheme: String,
+    host: Host,
+    port: Option<u16>,
+    path: String,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+impl Url {
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+    pub fn host(&self) -> &Host {
+        &self.host
+    }
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.scheme, self.host)?;
+        if let Some(port) = self.port {
+            write!(f, ":{}", port)?;
+        }
+        write!(f, "{}", self.path)?;
+        if let Some(query) = &self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+impl str::FromStr for Url {
+    type Err = UrlError;
+    fn from_str(s: &str) -> Result<Url, UrlError> {
+        let s = s.trim();
+        if let Some(c) = s.chars().find(|c| c.is_whitespace() || c.is_control()) {
+            return Err(UrlError::InvalidCharacter(c));
+        }
+        let (scheme, rest) = s.split_once("://").ok_or(UrlError::MissingScheme)?;
+        let valid_scheme = scheme
+            .chars()
+            .next()
+            .map(|c| c.is_ascii_alphabetic())
+            .unwrap_or(false)
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+        if !valid_scheme {
+            return Err(UrlError::InvalidScheme(scheme.to_string()));
+        }
+        let (rest, fragment) = match rest.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment.to_string())),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query.to_string())),
+            None => (rest, None),
+        };
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, ""),
+        };
+        if authority.contains('@') {
+            return Err(UrlError::CredentialsNotSupported);
+        }
+        let (host, port) = if let Some(bracketed) = authority.strip_prefix('[') {
+            let (ip, after) = bracketed
+                .split_once(']')
+                .ok_or_else(|| UrlError::InvalidIpv6(authority.to_string()))?;
+            let ip = ip
+                .parse::<net::Ipv6Addr>()
+                .map_err(|_| UrlError::InvalidIpv6(ip.to_string()))?;
+            let port = match after {
+                "" => None,
+                after => Some(after.strip_prefix(':').unwrap_or(after)),
+            };
+            (Host::Ip(net::IpAddr::V6(ip)), port)
+        } else {
+            let (host, port) = match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            };
+            if host.is_empty() {
+                return Err(UrlError::MissingHost);
+            }
+            let host = match host.parse::<net::Ipv4Addr>() {
+                Ok(ip) => Host::Ip(net::IpAddr::V4(ip)),
+                Err(_) => Host::Domain(host.parse().map_err(UrlError::Host)?),
+            };
+            (host, port)
+        };
+        let port = port
+            .map(|port| {
+                port.parse::<u16>()
+                    .map_err(|_| UrlError::InvalidPort(port.to_string()))
+            })
+            .transpose()?;
+        Ok(Url {
+            scheme: scheme.to_ascii_lowercase(),
+            host,
+            port,
+            path: path.to_string(),
+            query,
+            fragment,
+        })
+    }
+}
+
+/// A URL input that only accepts the given schemes, eg `https` for links shown to other users.
+/// Schemes are compared case-insensitively.
+pub struct UrlInput {
+    url: crate::inputs::Scalar<Url, UrlError>,
+    schemes: Vec<String>,
+}
+
+impl UrlInput {
+    pub fn new(url: crate::inputs::Scalar<Url, UrlError>, schemes: Vec<String>) -> UrlInput {
+        UrlInput {
+            url,
+            schemes: schemes.iter().map(|s| s.to_ascii_lowercase()).collect(),
+        }
+    }
+    pub fn get_url(&self) -> &crate::inputs::Scalar<Url, UrlError> {
+        &self.url
+    }
+    pub fn get_schemes(&self) -> &[String] {
+        &self.schemes
+    }
+}
+
+impl crate::SetInput for UrlInput {
+    fn set(&mut self, data: Url) {
+        self.url.set(data);
+    }
+}
+
+impl crate::UserInput for UrlInput {
+    type Output = Url;
+    type Input = String;
+    fn update(&mut self, input: Self::Input) {
+        self.url.update(input);
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        // check the scheme before the validations so they only see allowed URLs
+        let url: Url = self.url.get_input().parse()?;
+        if !self.schemes.contains(&url.scheme) {
+            return Err(UrlError::SchemeNotAllowed {
+                scheme: url.scheme,
+                allowed: self.schemes.clone(),
+            }
+            .into());
+        }
+        self.url.parse()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkError {
+    InvalidAddress(String),
+    WrongFamily(String),
+    InvalidPrefix(String),
+    PrefixTooLong { max: u8, actual: u8 },
+    HostBitsSet { suggestion: String },
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::InvalidAddress(address) => {
+                write!(f, "'{}' is not a valid IP address", address)
+            }
+            NetworkError::WrongFamily(address) => {
+                write!(f, "'{}' is not the expected kind of IP address", address)
+            }
+            NetworkError::InvalidPrefix(prefix) => {
+                write!(f, "'{}' is not a valid prefix length", prefix)
+            }
+            NetworkError::PrefixTooLong { max, actual } => write!(
+                f,
+                "the prefix length can be at most {} but was {}",
+                max, actual
+            ),
+            NetworkError::HostBitsSet { suggestion } => write!(
+                f,
+                "the address has bits set after the prefix, did you mean {}?",
+                suggestion
+            ),
+        }
+    }
+}
+
+impl error::Error for NetworkError {}
+
+/// An IPv4 network in CIDR notation, eg `10.0.0.0/8`. A bare address is a single host (`/32`).
+/// Addresses with bits set after the prefix (eg `10.0.0.1/8`) are rejected, as in a firewall rule
+/// they are almost always a typo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv4Network {
+    address: net::Ipv4Addr,
+    prefix: u8,
+}
+
+impl Ipv4Network {
+    pub fn new(address: net::Ipv4Addr, prefix: u8) -> Result<Ipv4Network, NetworkError> {
+        if prefix > 32 {
+            return Err(NetworkError::PrefixTooLong {
+                max: 32,
+                actual: prefix,
+            });
+        }
+        let network = Ipv4Network {
+            address: net::Ipv4Addr::from(u32::from(address) & v4_mask(prefix)),
+            prefix,
+        };
+        if network.address != address {
+            return Err(NetworkError::HostBitsSet {
+                suggestion: network.to_string(),
+            });
+        }
+        Ok(network)
+    }
+    pub fn address(&self) -> net::Ipv4Addr {
+        self.address
+    }
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+    pub fn netmask(&self) -> net::Ipv4Addr {
+        net::Ipv4Addr::from(v4_mask(self.prefix))
+    }
+    pub fn contains(&self, address: net::Ipv4Addr) -> bool {
+        u32::from(address) & v4_mask(self.prefix) == u32::from(self.address)
+    }
+}
+
+fn v4_mask(prefix: u8) -> u32 {
+    u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0)
+}
+
+impl fmt::Display for Ipv4Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
+
+impl str::FromStr for Ipv4Network {
+    type Err = NetworkError;
+    fn from_str(s: &str) -> Result<Ipv4Network, NetworkError> {
+        let CidrParts(address, prefix) = s.trim().parse()?;
+        match address {
+            net::IpAddr::V4(address) => Ipv4Network::new(address, prefix.unwrap_or(32)),
+            net::IpAddr::V6(_) => Err(NetworkError::WrongFamily(s.trim().to_string())),
+        }
+    }
+}
+
+/// An IPv6 network in CIDR notation, eg `2001:db8::/32`. A bare address is a single host
+/// (`/128`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ipv6Network {
+    address: net::Ipv6Addr,
+    prefix: u8,
+}
+
+impl Ipv6Network {
+    pub fn new(address: net::Ipv6Addr, prefix: u8) -> Result<Ipv6Network, NetworkError> {
+        if prefix > 128 {
+            return Err(NetworkError::PrefixTooLong {
+                max: 128,
+                actual: prefix,
+            });
+        }
+        let network = Ipv6Network {
+            address: net::Ipv6Addr::from(u128::from(address) & v6_mask(prefix)),
+            prefix,
+        };
+        if network.address != address {
+            return Err(NetworkError::HostBitsSet {
+                suggestion: network.to_string(),
+            });
+        }
+        Ok(network)
+    }
+    pub fn address(&self) -> net::Ipv6Addr {
+        self.address
+    }
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+    pub fn contains(&self, address: net::Ipv6Addr) -> bool {
+        u128::from(address) & v6_mask(self.prefix) == u128::from(self.address)
+    }
+}
+
+fn v6_mask(prefix: u8) -> u128 {
+    u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0)
+}
+
+impl fmt::Display for Ipv6Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
+
+impl str::FromStr for Ipv6Network {
+    type Err = NetworkError;
+    fn from_str(s: &str) -> Result<Ipv6Network, NetworkError> {
+        let CidrParts(address, prefix) = s.trim().parse()?;
+        match address {
+            net::IpAddr::V6(address) => Ipv6Network::new(address, prefix.unwrap_or(128)),
+            net::IpAddr::V4(_) => Err(NetworkError::WrongFamily(s.trim().to_string())),
+        }
+    }
+}
+
+/// Either an IPv4 or an IPv6 network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IpNetwork {
+    V4(Ipv4Network),
+    V6(Ipv6Network),
+}
+
+impl IpNetwork {
+    pub fn contains(&self, address: net::IpAddr) -> bool {
+        match (self, address) {
+            (IpNetwork::V4(network), net::IpAddr::V4(address)) => network.contains(address),
+            (IpNetwork::V6(network), net::IpAddr::V6(address)) => network.contains(address),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpNetwork::V4(network) => write!(f, "{}", network),
+            IpNetwork::V6(network) => write!(f, "{}", network),
+        }
+    }
+}
+
+impl str::FromStr for IpNetwork {
+    type Err = NetworkError;
+    fn from_str(s: &str) -> Result<IpNetwork, NetworkError> {
+        let CidrParts(address, prefix) = s.trim().parse()?;
+        match address {
+            net::IpAddr::V4(address) => Ok(IpNetwork::V4(Ipv4Network::new(
+                address,
+                prefix.unwrap_or(32),
+            )?)),
+            net::IpAddr::V6(address) => Ok(IpNetwork::V6(Ipv6Network::new(
+                address,
+                prefix.unwrap_or(128),
+            )?)),
+        }
+    }
+}
+
+// the address and optional prefix length of `address/prefix`
+struct CidrParts(net::IpAddr, Option<u8>);
+
+impl str::FromStr for CidrParts {
+    type Err = NetworkError;
+    fn from_str(s: &str) -> Result<CidrParts, NetworkError> {
+        let (address, prefix) = match s.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (s, None),
+        };
+        let address = address
+            .parse()
+            .map_err(|_| NetworkError::InvalidAddress(address.to_string()))?;
+        let prefix = prefix
+            .map(|prefix| {
+                prefix
+                    .parse()
+                    .map_err(|_| NetworkError::InvalidPrefix(prefix.to_string()))
+            })
+            .transpose()?;
+        Ok(CidrParts(address, prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UserInput;
+
+    #[test]
+    fn parses_hostnames() {
+        let hostname = "Mail.Example.COM.".parse::<Hostname>().unwrap();
+        assert_eq!(hostname.as_str(), "mail.example.com");
+        assert_eq!(
+            hostname.labels().collect::<Vec<_>>(),
+            vec!["mail", "example", "com"]
+        );
+        assert!("localhost".parse::<Hostname>().is_ok());
+        assert!("a-b.c0m".parse::<Hostname>().is_ok());
+        let long_label = format!("{}.com", "a".repeat(64));
+        for (input, error) in [
+            ("", HostnameError::Empty),
+            ("a..b", HostnameError::EmptyLabel),
+            (
+                long_label.as_str(),
+                HostnameError::LabelTooLong("a".repeat(64)),
+            ),
+            (
+                "a_b.com",
+                HostnameError::InvalidCharacter {
+                    label: "a_b".to_string(),
+                    character: '_',
+                },
+            ),
+            ("-a.com", HostnameError::HyphenAtEdge("-a".to_string())),
+            ("1.2.3.4", HostnameError::NumericTopLevel("4".to_string())),
+        ]
+        .iter()
+        {
+            assert_eq!(&input.parse::<Hostname>().unwrap_err(), error, "{}", input);
+        }
+    }
+
+    #[test]
+    fn parses_emails() {
+        let email = "First.Last+tag@Example.com".parse::<Email>().unwrap();
+        assert_eq!(email.local_part(), "First.Last+tag");
+        assert_eq!(email.to_string(), "First.Last+tag@example.com");
+        assert_eq!(email.to_string().parse::<Email>().unwrap(), email);
+        for (input, error) in [
+            ("example.com", EmailError::MissingAt),
+            ("@example.com", EmailError::EmptyLocalPart),
+            ("a b@example.com", EmailError::InvalidLocalCharacter(' ')),
+            ("a..b@example.com", EmailError::MisplacedDot),
+            (".a@example.com", EmailError::MisplacedDot),
+            ("a@", EmailError::Domain(HostnameError::Empty)),
+        ]
+        .iter()
+        {
+            assert_eq!(&input.parse::<Email>().unwrap_err(), error, "{}", input);
+        }
+    }
+
+    #[test]
+    fn parses_urls() {
+        let url = "HTTPS://Example.com:8443/a/B?x=1#top"
+            .parse::<Url>()
+            .unwrap();
+        assert_eq!(url.scheme(), "https");
+        assert_eq!(url.port(), Some(8443));
+        assert_eq!(url.path(), "/a/B");
+        assert_eq!(url.query(), Some("x=1"));
+        assert_eq!(url.fragment(), Some("top"));
+        assert_eq!(url.to_string(), "https://example.com:8443/a/B?x=1#top");
+        for input in [
+            "http://[::1]:8080/",
+            "ftp://10.0.0.1",
+            "https://example.com",
+        ]
+        .iter()
+        {
+            let url = input.parse::<Url>().unwrap();
+            assert_eq!(&url.to_string(), input);
+            assert_eq!(url.to_string().parse::<Url>().unwrap(), url);
+        }
+        for (input, error) in [
+            ("example.com", UrlError::MissingScheme),
+            (
+                "1http://example.com",
+                UrlError::InvalidScheme("1http".to_string()),
+            ),
+            ("https://", UrlError::MissingHost),
+            (
+                "https://user@example.com",
+                UrlError::CredentialsNotSupported,
+            ),
+            (
+                "https://example.com:http",
+                UrlError::InvalidPort("http".to_string()),
+            ),
+            (
+                "https://example.com:70000",
+                UrlError::InvalidPort("70000".to_string()),
+            ),
+            ("https://[::g]/", UrlError::InvalidIpv6("::g".to_string())),
+            ("https://exa mple.com", UrlError::InvalidCharacter(' ')),
+        ]
+        .iter()
+        {
+            assert_eq!(&input.parse::<Url>().unwrap_err(), error, "{}", input);
+        }
+    }
+
+    #[test]
+    fn checks_url_schemes() {
+        let url = "https://example.com".parse::<Url>().unwrap();
+        let mut input = UrlInput::new(
+            crate::inputs::Scalar::new(&url, crate::Validations::new()),
+            vec!["HTTPS".to_string()],
+        );
+        assert_eq!(input.parse().unwrap(), url);
+        input.update("http://example.com".to_string());
+        assert!(input.parse().is_err());
+    }
+
+    #[test]
+    fn parses_networks() {
+        for input in ["10.0.0.0/8", "192.168.1.1/32", "0.0.0.0/0"].iter() {
+            let network = input.parse::<Ipv4Network>().unwrap();
+            assert_eq!(&network.to_string(), input);
+        }
+        let network = "10.1.0.0/16".parse::<Ipv4Network>().unwrap();
+        assert_eq!(network.netmask(), net::Ipv4Addr::new(255, 255, 0, 0));
+        assert!(network.contains(net::Ipv4Addr::new(10, 1, 200, 3)));
+        assert!(!network.contains(net::Ipv4Addr::new(10, 2, 0, 0)));
+        assert_eq!("192.168.1.1".parse::<Ipv4Network>().unwrap().prefix(), 32);
+        let network = "2001:DB8::/32".parse::<IpNetwork>().unwrap();
+        assert_eq!(network.to_string(), "2001:db8::/32");
+        assert!(network.contains("2001:db8::1".parse().unwrap()));
+        assert!(!network.contains("10.0.0.1".parse().unwrap()));
+        assert_eq!("::1".parse::<Ipv6Network>().unwrap().prefix(), 128);
+    }
+
+    #[test]
+    fn rejects_networks() {
+        assert_eq!(
+            "10.0.0.1/8".parse::<Ipv4Network>(),
+            Err(NetworkError::HostBitsSet {
+                suggestion: "10.0.0.0/8".to_string()
+            })
+        );
+        assert_eq!(
+            "10.0.0.0/33".parse::<IpNetwork>(),
+            Err(NetworkError::PrefixTooLong {
+                max: 32,
+                actual: 33
+            })
+        );
+        assert_eq!(
+            "::/129".parse::<Ipv6Network>(),
+            Err(NetworkError::PrefixTooLong {
+                max: 128,
+                actual: 129
+            })
+        );
+        assert_eq!(
+            "::1".parse::<Ipv4Network>(),
+            Err(NetworkError::WrongFamily("::1".to_string()))
+        );
+        assert_eq!(
+            "10.0.0.256/8".parse::<Ipv4Network>(),
+            Err(NetworkError::InvalidAddress("10.0.0.256".to_string()))
+        );
+        assert_eq!(
+            "10.0.0.0/x".parse::<Ipv4Network>(),
+            Err(NetworkError::InvalidPrefix("x".to_string()))
+        );
+    }
+}
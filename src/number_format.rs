@@ -0,0 +1,193 @@
+use std::{error, fmt};
+
+#[derive(Debug)]
+pub struct NumberFormatError {
+    input: String,
+    reason: String,
+}
+
+impl NumberFormatError {
+    pub fn new(input: String, reason: String) -> NumberFormatError {
+        NumberFormatError { input, reason }
+    }
+}
+
+impl fmt::Display for NumberFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a valid number: {}", self.input, self.reason)
+    }
+}
+
+impl error::Error for NumberFormatError {}
+
+/// How numbers are written for a locale. Parsing converts to the plain form accepted by the
+/// `FromStr` impls of the numeric types (eg `-1234.56`) and formatting converts back from their
+/// `Display` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    decimal_separator: char,
+    grouping_separator: Option<char>,
+    currency_symbol: Option<String>,
+    currency_after: bool,
+    negative_parentheses: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> NumberFormat {
+        NumberFormat::new('.', Some(','))
+    }
+}
+
+impl NumberFormat {
+    pub fn new(decimal_separator: char, grouping_separator: Option<char>) -> NumberFormat {
+        NumberFormat {
+            decimal_separator,
+            grouping_separator,
+            currency_symbol: None,
+            currency_after: false,
+            negative_parentheses: false,
+        }
+    }
+    /// `1,234.56`
+    pub fn english() -> NumberFormat {
+        NumberFormat::new('.', Some(','))
+    }
+    /// `1.234,56`
+    pub fn european() -> NumberFormat {
+        NumberFormat::new(',', Some('.'))
+    }
+    /// Allows `symbol` before or after the number when parsing, and formats with it before the
+    /// number, or after it when `after` is set.
+    pub fn with_currency(self, symbol: &str, after: bool) -> NumberFormat {
+        NumberFormat {
+            currency_symbol: Some(symbol.to_string()),
+            currency_after: after,
+            ..self
+        }
+    }
+    /// Accepts and formats negative numbers as `(1,234.56)`.
+    pub fn with_negative_parentheses(self) -> NumberFormat {
+        NumberFormat {
+            negative_parentheses: true,
+            ..self
+        }
+    }
+    pub fn get_decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+    pub fn get_grouping_separator(&self) -> Option<char> {
+        self.grouping_separator
+    }
+    pub fn get_currency_symbol(&self) -> Option<&str> {
+        self.currency_symbol.as_deref()
+    }
+    fn strip_currency<'a>(&self, input: &'a str) -> &'a str {
+        match &self.currency_symbol {
+            Some(symbol) => input
+                .strip_prefix(symbol.as_str())
+                .or_else(|| input.strip_suffix(symbol.as_str()))
+                .map(str::trim)
+                .unwrap_or(input),
+            None => input,
+        }
+    }
+    /// Converts `input` written in this format to the plain form, eg `(1.234,5 €)` to
+    /// `-1234.5`.
+    pub fn normalise(&self, input: &str) -> Result<String, NumberFormatError> {
+        let err = |reason: String| NumberFormatError::new(input.to_string(), reason);
+        let mut rest = self.strip_currency(input.trim());
+        let mut negative = false;
+        if self.negative_parentheses && rest.starts_with('(') && rest.ends_with(')') {
+            negative = true;
+            rest = self.strip_currency(rest[1..rest.len() - 1].trim());
+        }
+        if let Some(unsigned) = rest.strip_prefix('-') {
+            if negative {
+                return Err(err("it has both parentheses and a minus sign".to_string()));
+            }
+            negative = true;
+            rest = self.strip_currency(unsigned.trim_start());
+        }
+        let mut parts = rest.split(self.decimal_separator);
+        let integer = parts.next().unwrap_or_default();
+        let fraction = parts.next();
+        if parts.next().is_some() {
+            return Err(err(format!(
+                "it has more than one decimal separator {}",
+                self.decimal_separator
+            )));
+        }
+        let integer = match self.grouping_separator {
+            Some(grouping) if integer.contains(grouping) => {
+                let groups = integer.split(grouping).collect::<Vec<_>>();
+                let first = groups[0].len();
+                if first == 0 || first > 3 || groups[1..].iter().any(|group| group.len() != 3) {
+                    return Err(err(format!(
+                        "digits should be grouped in threes by {}",
+                        grouping
+                    )));
+                }
+                groups.concat()
+            }
+            _ => integer.to_string(),
+        };
+        if integer.is_empty() && fraction.unwrap_or("").is_empty() {
+            return Err(err("it has no digits".to_string()));
+        }
+        let all_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if !all_digits(&integer) || !fraction.into_iter().all(all_digits) {
+            return Err(err(
+                "it contains characters other than digits and separators".to_string(),
+            ));
+        }
+        let mut normalised = String::new();
+        if negative {
+            normalised.push('-');
+        }
+        normalised.push_str(if integer.is_empty() { "0" } else { &integer });
+        if let Some(fraction) = fraction.filter(|fraction| !fraction.is_empty()) {
+            normalised.push('.');
+            normalised.push_str(fraction);
+        }
+        Ok(normalised)
+    }
+    /// Converts a number in the plain form (as produced by `Display` for the numeric types) to
+    /// this format. Anything else is returned unchanged.
+    pub fn format(&self, plain: &str) -> String {
+        let (negative, unsigned) = match plain.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, plain),
+        };
+        let mut parts = unsigned.splitn(2, '.');
+        let integer = parts.next().unwrap_or_default();
+        let fraction = parts.next();
+        if integer.is_empty() || !integer.chars().all(|c| c.is_ascii_digit()) {
+            return plain.to_string();
+        }
+        let mut formatted = String::new();
+        for (idx, digit) in integer.chars().enumerate() {
+            if idx > 0 && (integer.len() - idx) % 3 == 0 {
+                if let Some(grouping) = self.grouping_separator {
+                    formatted.push(grouping);
+                }
+            }
+            formatted.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            formatted.push(self.decimal_separator);
+            formatted.push_str(fraction);
+        }
+        if let Some(symbol) = &self.currency_symbol {
+            formatted = if self.currency_after {
+                format!("{} {}", formatted, symbol)
+            } else {
+                format!("{}{}", symbol, formatted)
+            };
+        }
+        match (negative, self.negative_parentheses) {
+            (true, true) => format!("({})", formatted),
+            (true, false) => format!("-{}", formatted),
+            (false, _) => formatted,
+        }
+    }
+}
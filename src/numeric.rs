@@ -0,0 +1,787 @@
+use crate::inputs;
+use std::{error, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Bankers rounding, eg 2.5 to 2 and 3.5 to 4
+    HalfEven,
+    /// eg 2.5 to 3 and -2.5 to -3
+    HalfUp,
+    Truncate,
+}
+
+impl Rounding {
+    pub fn strategy(&self) -> rust_decimal::RoundingStrategy {
+        match self {
+            Rounding::HalfEven => rust_decimal::RoundingStrategy::MidpointNearestEven,
+            Rounding::HalfUp => rust_decimal::RoundingStrategy::MidpointAwayFromZero,
+            Rounding::Truncate => rust_decimal::RoundingStrategy::ToZero,
+        }
+    }
+}
+
+/// What to do with a value that has more decimal places than the scale allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExcessDigits {
+    Reject,
+    Round(Rounding),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecisionError {
+    TooManyDecimalPlaces { max: u32, actual: u32 },
+    TooManyIntegerDigits { max: u32, actual: u32 },
+}
+
+impl fmt::Display for PrecisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrecisionError::TooManyDecimalPlaces { max, actual } => write!(
+                f,
+                "at most {} decimal places are allowed but there were {}",
+                max, actual
+            ),
+            PrecisionError::TooManyIntegerDigits { max, actual } => write!(
+                f,
+                "at most {} digits are allowed before the decimal point but there were {}",
+                max, actual
+            ),
+        }
+    }
+}
+
+impl error::Error for PrecisionError {}
+
+/// A decimal with a maximum precision (total digits) and scale (digits after the decimal point),
+/// like a SQL `NUMERIC(precision, scale)` column. Parsed values are always returned with exactly
+/// `scale` decimal places.
+///
+/// Values with too many decimal places are either rejected or rounded, in which case
+/// `NumericMsg::Normalise` rewrites the raw input to the rounded value (eg on blur). Errors are
+/// a `PrecisionError` in `Error::Parse`, so they can be downcast by the caller.
+pub struct Numeric {
+    decimal: inputs::Decimal,
+    precision: u32,
+    scale: u32,
+    excess: ExcessDigits,
+}
+
+pub enum NumericMsg {
+    Input(String),
+    Normalise,
+}
+
+impl Numeric {
+    pub fn new(
+        decimal: inputs::Decimal,
+        precision: u32,
+        scale: u32,
+        excess: ExcessDigits,
+    ) -> Numeric {
+        Numeric {
+            decimal,
+            precision,
+            scale,
+            excess,
+        }
+    }
+    pub fn get_decimal(&self) -> &inputs::Decimal {
+        &self.decimal
+    }
+    pub fn get_precision(&self) -> u32 {
+        self.precision
+    }
+    pub fn get_scale(&self) -> u32 {
+        self.scale
+    }
+    /// Rounds (if allowed) and rescales `value` to fit the precision and scale.
+    pub fn fit(
+        &self,
+        value: rust_decimal::Decimal,
+    ) -> Result<rust_decimal::Decimal, PrecisionError> {
+        let places = value.normalize().scale();
+        let mut fitted = if places > self.scale {
+            match self.excess {
+                ExcessDigits::Reject => {
+                    return Err(PrecisionError::TooManyDecimalPlaces {
+                        max: self.scale,
+                        actual: places,
+                    })
+                }
+                ExcessDigits::Round(rounding) => {
+                    value.round_dp_with_strategy(self.scale, rounding.strategy())
+                }
+            }
+        } else {
+            value
+        };
+        fitted.rescale(self.scale);
+        let integer = fitted.trunc().abs();
+        let integer_digits = if integer.is_zero() {
+            0
+        } else {
+            integer.to_string().len() as u32
+        };
+        let max = self.precision.saturating_sub(self.scale);
+        if integer_digits > max {
+            return Err(PrecisionError::TooManyIntegerDigits {
+                max,
+                actual: integer_digits,
+            });
+        }
+        Ok(fitted)
+    }
+}
+
+impl crate::SetInput for Numeric {
+    fn set(&mut self, data: rust_decimal::Decimal) {
+        self.decimal.set(data);
+    }
+}
+
+impl crate::UserInput for Numeric {
+    type Output = rust_decimal::Decimal;
+    type Input = NumericMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            NumericMsg::Input(input) => self.decimal.update(input),
+            NumericMsg::Normalise => {
+                if let Ok(fitted) = self.parse() {
+                    self.decimal.set(fitted);
+                }
+            }
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        Ok(self.fit(self.decimal.parse()?)?)
+    }
+}
+
+/// Numbers that can be checked against a step, for `NumberConstraints`.
+pub trait Stepped: PartialOrd + fmt::Display + Sized {
+    /// Whether `self` is a whole number of `step`s away from `base`.
+    fn is_multiple_of_step(&self, base: &Self, step: &Self) -> bool;
+    fn zero() -> Self;
+}
+
+macro_rules! impl_stepped {
+    ($($t:ty),*) => {
+        $(
+            impl Stepped for $t {
+                fn is_multiple_of_step(&self, base: &$t, step: &$t) -> bool {
+                    if *step == Self::zero() {
+                        return true;
+                    }
+                    // compare in the wider i128 so that eg `u8` values below base don't underflow
+                    (*self as i128 - *base as i128) % (*step as i128) == 0
+                }
+                fn zero() -> $t {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_stepped!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl Stepped for rust_decimal::Decimal {
+    fn is_multiple_of_step(
+        &self,
+        base: &rust_decimal::Decimal,
+        step: &rust_decimal::Decimal,
+    ) -> bool {
+        if step.is_zero() {
+            return true;
+        }
+        // values too far from the base to subtract aren't on a step
+        self.checked_sub(*base)
+            .and_then(|offset| offset.checked_rem(*step))
+            .map(|remainder| remainder.is_zero())
+            .unwrap_or(false)
+    }
+    fn zero() -> rust_decimal::Decimal {
+        rust_decimal::Decimal::new(0, 0)
+    }
+}
+
+/// Declarative range and step constraints for a `Scalar`, eg for an HTML `<input type=number
+/// min=.. max=.. step=..>`. As in HTML, valid values are a whole number of steps from `min`,
+/// or from zero if there is no `min`. The checks are chosen when the constraints are built, so
+/// that a `Scalar` can hold them without needing the `Stepped` bound itself.
+#[derive(Debug, Clone)]
+pub struct NumberConstraints<O> {
+    min: Option<O>,
+    max: Option<O>,
+    step: Option<O>,
+    check: fn(&NumberConstraints<O>, &O) -> crate::ValidationResult,
+}
+
+impl<O: Stepped> NumberConstraints<O> {
+    pub fn new(min: Option<O>, max: Option<O>, step: Option<O>) -> NumberConstraints<O> {
+        NumberConstraints {
+            min,
+            max,
+            step,
+            check: check_constraints::<O>,
+        }
+    }
+}
+
+impl<O> NumberConstraints<O> {
+    pub fn get_min(&self) -> Option<&O> {
+        self.min.as_ref()
+    }
+    pub fn get_max(&self) -> Option<&O> {
+        self.max.as_ref()
+    }
+    pub fn get_step(&self) -> Option<&O> {
+        self.step.as_ref()
+    }
+    pub fn check(&self, value: &O) -> crate::ValidationResult {
+        (self.check)(self, value)
+    }
+}
+
+fn check_constraints<O: Stepped>(
+    constraints: &NumberConstraints<O>,
+    value: &O,
+) -> crate::ValidationResult {
+    crate::check_bounds(
+        "Value",
+        ("at least", "at most"),
+        value,
+        (constraints.min.as_ref(), constraints.max.as_ref()),
+        ToString::to_string,
+    )?;
+    if let Some(step) = &constraints.step {
+        let zero = O::zero();
+        let base = constraints.min.as_ref().unwrap_or(&zero);
+        if !value.is_multiple_of_step(base, step) {
+            return Err(if *base != zero {
+                format!(
+                    "Value should be {} plus a multiple of {} but was {}",
+                    base, step, value
+                )
+            } else {
+                format!("Value should be a multiple of {} but was {}", step, value)
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A range for filtering on, eg a price "from 10 to 250". Either end may be open, and both ends
+/// are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumberRange<O> {
+    pub low: Option<O>,
+    pub high: Option<O>,
+}
+
+impl<O: PartialOrd> NumberRange<O> {
+    pub fn contains(&self, value: &O) -> bool {
+        !matches!(&self.low, Some(low) if value < low)
+            && !matches!(&self.high, Some(high) if value > high)
+    }
+    pub fn is_unbounded(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+/// A two-ended numeric range, eg for a range slider. Both ends share the same constraints, and
+/// leaving an end blank makes it open.
+pub struct NumericRange<O, E>
+where
+    O: std::str::FromStr<Err = E> + fmt::Display + PartialOrd + Clone,
+    E: error::Error + Sync + Send + 'static,
+{
+    low: inputs::Scalar<O, E>,
+    high: inputs::Scalar<O, E>,
+    range_validations: crate::Validations<NumberRange<O>>,
+}
+
+impl<O, E> NumericRange<O, E>
+where
+    O: std::str::FromStr<Err = E> + fmt::Display + PartialOrd + Clone,
+    E: error::Error + Sync + Send + 'static,
+{
+    pub fn new(
+        data: NumberRange<O>,
+        constraints: Option<NumberConstraints<O>>,
+        range_validations: crate::Validations<NumberRange<O>>,
+    ) -> NumericRange<O, E> {
+        NumericRange {
+            low: end_input(data.low, constraints.clone()),
+            high: end_input(data.high, constraints),
+            range_validations,
+        }
+    }
+    pub fn get_low(&self) -> &inputs::Scalar<O, E> {
+        &self.low
+    }
+    pub fn get_high(&self) -> &inputs::Scalar<O, E> {
+        &self.high
+    }
+    /// The constraints shared by both ends, eg for the bounds and step of the slider.
+    pub fn get_constraints(&self) -> Option<&NumberConstraints<O>> {
+        self.low.get_constraints()
+    }
+    pub fn set_constraints(&mut self, constraints: Option<NumberConstraints<O>>) {
+        self.low.set_constraints(constraints.clone());
+        self.high.set_constraints(constraints);
+    }
+}
+
+fn end_input<O, E>(
+    data: Option<O>,
+    constraints: Option<NumberConstraints<O>>,
+) -> inputs::Scalar<O, E>
+where
+    O: std::str::FromStr<Err = E> + fmt::Display,
+    E: error::Error + Sync + Send + 'static,
+{
+    let mut input = inputs::Scalar::new_blank(crate::Validations::new());
+    if let Some(data) = data {
+        input.set(data);
+    }
+    input.set_constraints(constraints);
+    input
+}
+
+fn parse_end<O, E>(input: &inputs::Scalar<O, E>) -> crate::Result<Option<O>>
+where
+    O: std::str::FromStr<Err = E> + fmt::Display,
+    E: error::Error + Sync + Send + 'static,
+{
+    if input.get_input().trim().is_empty() {
+        Ok(None)
+    } else {
+        crate::UserInput::parse(input).map(Some)
+    }
+}
+
+pub enum NumericRangeMsg {
+    Low(String),
+    High(String),
+}
+
+impl<O, E> crate::UserInput for NumericRange<O, E>
+where
+    O: std::str::FromStr<Err = E> + fmt::Display + PartialOrd + Clone,
+    E: error::Error + Sync + Send + 'static,
+{
+    type Output = NumberRange<O>;
+    type Input = NumericRangeMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            NumericRangeMsg::Low(input) => self.low.update(input),
+            NumericRangeMsg::High(input) => self.high.update(input),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let low = parse_end(&self.low)?;
+        let high = parse_end(&self.high)?;
+        if let (Some(low), Some(high)) = (&low, &high) {
+            if high < low {
+                return Err(crate::Error::Validation(
+                    vec![format!("High {} should not be below low {}", high, low)].into(),
+                ));
+            }
+        }
+        let range = NumberRange { low, high };
+        self.range_validations.validate(&range)?;
+        Ok(range)
+    }
+}
+
+impl<O, E> crate::SetInput for NumericRange<O, E>
+where
+    O: std::str::FromStr<Err = E> + fmt::Display + PartialOrd + Clone,
+    E: error::Error + Sync + Send + 'static,
+{
+    fn set(&mut self, data: NumberRange<O>) {
+        match data.low {
+            Some(low) => self.low.set(low),
+            None => crate::UserInput::update(&mut self.low, String::new()),
+        }
+        match data.high {
+            Some(high) => self.high.set(high),
+            None => crate::UserInput::update(&mut self.high, String::new()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+impl Radix {
+    pub fn all() -> Vec<Radix> {
+        vec![
+            Radix::Binary,
+            Radix::Octal,
+            Radix::Decimal,
+            Radix::Hexadecimal,
+        ]
+    }
+    pub fn base(&self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+        }
+    }
+    /// The lowercase prefix, eg `0x`. Prefixes are matched case-insensitively.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Radix::Binary => "0b",
+            Radix::Octal => "0o",
+            Radix::Decimal => "",
+            Radix::Hexadecimal => "0x",
+        }
+    }
+    fn from_prefix(input: &str) -> Option<(Radix, &str)> {
+        let prefix = input.get(..2)?.to_ascii_lowercase();
+        [Radix::Binary, Radix::Octal, Radix::Hexadecimal]
+            .iter()
+            .find(|radix| radix.prefix() == prefix)
+            .map(|radix| (*radix, &input[2..]))
+    }
+}
+
+impl fmt::Display for Radix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Radix::Binary => write!(f, "binary"),
+            Radix::Octal => write!(f, "octal"),
+            Radix::Decimal => write!(f, "decimal"),
+            Radix::Hexadecimal => write!(f, "hexadecimal"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RadixError {
+    Empty,
+    UnsupportedRadix(Radix),
+    InvalidDigits { radix: Radix, digits: String },
+    MisplacedUnderscore,
+    OutOfRange,
+}
+
+impl fmt::Display for RadixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RadixError::Empty => write!(f, "no digits were entered"),
+            RadixError::UnsupportedRadix(radix) => write!(f, "{} numbers are not accepted", radix),
+            RadixError::InvalidDigits { radix, digits } => {
+                write!(f, "'{}' is not a valid {} number", digits, radix)
+            }
+            RadixError::MisplacedUnderscore => {
+                write!(f, "underscores are only allowed between digits")
+            }
+            RadixError::OutOfRange => write!(f, "the number is out of range"),
+        }
+    }
+}
+
+impl error::Error for RadixError {}
+
+/// Integers that a `RadixInteger` input can hold. Values are converted through `i128`.
+pub trait RadixInt: Copy + fmt::Display {
+    fn from_i128(value: i128) -> Option<Self>;
+    fn to_i128(self) -> i128;
+}
+
+macro_rules! impl_radix_int {
+    ($($t:ty),*) => {
+        $(
+            impl RadixInt for $t {
+                fn from_i128(value: i128) -> Option<$t> {
+                    use std::convert::TryFrom;
+                    <$t>::try_from(value).ok()
+                }
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Parses `input` as an integer, with an optional sign and a `0x`, `0o` or `0b` prefix. Digits
+/// without a prefix are read in `unprefixed`.
+pub fn parse_radix<I: RadixInt>(
+    input: &str,
+    accepted: &[Radix],
+    unprefixed: Radix,
+    allow_underscores: bool,
+) -> Result<I, RadixError> {
+    let input = input.trim();
+    let (negative, unsigned) = match input.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, input.strip_prefix('+').unwrap_or(input)),
+    };
+    let (radix, digits) = Radix::from_prefix(unsigned).unwrap_or((unprefixed, unsigned));
+    if !accepted.contains(&radix) {
+        return Err(RadixError::UnsupportedRadix(radix));
+    }
+    let digits = if allow_underscores && digits.contains('_') {
+        if digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") {
+            return Err(RadixError::MisplacedUnderscore);
+        }
+        digits.replace('_', "")
+    } else {
+        digits.to_string()
+    };
+    if digits.is_empty() {
+        return Err(RadixError::Empty);
+    }
+    if !digits.chars().all(|c| c.is_digit(radix.base())) {
+        return Err(RadixError::InvalidDigits { radix, digits });
+    }
+    let magnitude =
+        i128::from_str_radix(&digits, radix.base()).map_err(|_| RadixError::OutOfRange)?;
+    I::from_i128(if negative { -magnitude } else { magnitude }).ok_or(RadixError::OutOfRange)
+}
+
+/// Formats `value` in `radix` with its prefix and uppercase digits, eg `0x1F` or `-0b101`.
+pub fn format_radix<I: RadixInt>(value: I, radix: Radix) -> String {
+    let value = value.to_i128();
+    let sign = if value < 0 { "-" } else { "" };
+    let magnitude = value.unsigned_abs();
+    let digits = match radix {
+        Radix::Binary => format!("{:b}", magnitude),
+        Radix::Octal => format!("{:o}", magnitude),
+        Radix::Decimal => format!("{}", magnitude),
+        Radix::Hexadecimal => format!("{:X}", magnitude),
+    };
+    format!("{}{}{}", sign, radix.prefix(), digits)
+}
+
+/// An integer accepting `0x1F`, `0b1010` or `0o17` style input, eg for register addresses. Only
+/// the `accepted` radices parse, and digits without a prefix are read as decimal unless changed
+/// with `with_unprefixed`. The display radix is used to format values set on the input. Errors
+/// are a `RadixError` in `Error::Parse`.
+pub struct RadixInteger<I: RadixInt> {
+    input: String,
+    display: Radix,
+    unprefixed: Radix,
+    accepted: Vec<Radix>,
+    allow_underscores: bool,
+    validations: crate::Validations<I>,
+}
+
+impl<I: RadixInt> RadixInteger<I> {
+    pub fn new(
+        data: I,
+        display: Radix,
+        accepted: Vec<Radix>,
+        allow_underscores: bool,
+        validations: crate::Validations<I>,
+    ) -> RadixInteger<I> {
+        RadixInteger {
+            input: format_radix(data, display),
+            display,
+            unprefixed: Radix::Decimal,
+            accepted,
+            allow_underscores,
+            validations,
+        }
+    }
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+    /// Reads digits without a prefix in `unprefixed`, eg `Hexadecimal` so that `FF` parses.
+    /// Values are still shown in the display radix, so a `Decimal` display should keep a
+    /// `Decimal` unprefixed radix.
+    pub fn with_unprefixed(self, unprefixed: Radix) -> RadixInteger<I> {
+        RadixInteger { unprefixed, ..self }
+    }
+    pub fn get_display(&self) -> Radix {
+        self.display
+    }
+    pub fn get_unprefixed(&self) -> Radix {
+        self.unprefixed
+    }
+    pub fn get_accepted(&self) -> &[Radix] {
+        &self.accepted
+    }
+    pub fn allows_underscores(&self) -> bool {
+        self.allow_underscores
+    }
+}
+
+impl<I: RadixInt> crate::SetInput for RadixInteger<I> {
+    fn set(&mut self, data: I) {
+        self.input = format_radix(data, self.display);
+    }
+}
+
+impl<I: RadixInt> crate::UserInput for RadixInteger<I> {
+    type Output = I;
+    type Input = String;
+    fn update(&mut self, input: Self::Input) {
+        self.input = input;
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = parse_radix(
+            &self.input,
+            &self.accepted,
+            self.unprefixed,
+            self.allow_underscores,
+        )?;
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UserInput;
+
+    fn decimal(text: &str) -> rust_decimal::Decimal {
+        text.parse().unwrap()
+    }
+
+    fn numeric(excess: ExcessDigits) -> Numeric {
+        Numeric::new(
+            inputs::Decimal::new(&decimal("0"), crate::Validations::new()),
+            12,
+            2,
+            excess,
+        )
+    }
+
+    #[test]
+    fn fits_precision_and_scale() {
+        let numeric = numeric(ExcessDigits::Reject);
+        assert_eq!(
+            numeric.fit(decimal("1234.5")).unwrap().to_string(),
+            "1234.50"
+        );
+        assert_eq!(numeric.fit(decimal("-7.500")).unwrap().to_string(), "-7.50");
+        assert_eq!(
+            numeric.fit(decimal("9999999999.99")).unwrap().to_string(),
+            "9999999999.99"
+        );
+        assert_eq!(
+            numeric.fit(decimal("1.005")),
+            Err(PrecisionError::TooManyDecimalPlaces { max: 2, actual: 3 })
+        );
+        assert_eq!(
+            numeric.fit(decimal("12345678901")),
+            Err(PrecisionError::TooManyIntegerDigits {
+                max: 10,
+                actual: 11
+            })
+        );
+    }
+
+    #[test]
+    fn rounds() {
+        let fit = |rounding, value| {
+            numeric(ExcessDigits::Round(rounding))
+                .fit(decimal(value))
+                .map(|fitted| fitted.to_string())
+        };
+        assert_eq!(fit(Rounding::HalfEven, "1.005"), Ok("1.00".to_string()));
+        assert_eq!(fit(Rounding::HalfEven, "1.015"), Ok("1.02".to_string()));
+        assert_eq!(fit(Rounding::HalfUp, "-1.005"), Ok("-1.01".to_string()));
+        assert_eq!(fit(Rounding::Truncate, "1.009"), Ok("1.00".to_string()));
+        // rounding up can carry into a digit that doesn't fit
+        assert_eq!(
+            fit(Rounding::HalfUp, "9999999999.995"),
+            Err(PrecisionError::TooManyIntegerDigits {
+                max: 10,
+                actual: 11
+            })
+        );
+    }
+
+    #[test]
+    fn normalises_rounded_input() {
+        let mut numeric = numeric(ExcessDigits::Round(Rounding::HalfUp));
+        numeric.update(NumericMsg::Input("2.345".to_string()));
+        numeric.update(NumericMsg::Normalise);
+        assert_eq!(numeric.get_decimal().get_input(), "2.35");
+        assert_eq!(numeric.parse().unwrap(), decimal("2.35"));
+        numeric.update(NumericMsg::Input("abc".to_string()));
+        assert!(numeric.parse().is_err());
+    }
+
+    #[test]
+    fn parses_radix() {
+        let all = Radix::all();
+        let parse = |input| parse_radix::<u16>(input, &all, Radix::Decimal, true);
+        assert_eq!(parse("0x1F"), Ok(31));
+        assert_eq!(parse("0X1f"), Ok(31));
+        assert_eq!(parse("0b1010"), Ok(10));
+        assert_eq!(parse("0o17"), Ok(15));
+        assert_eq!(parse(" +42 "), Ok(42));
+        assert_eq!(parse("0b1111_0000"), Ok(240));
+        assert_eq!(
+            parse_radix::<u16>("FF", &all, Radix::Hexadecimal, false),
+            Ok(255)
+        );
+    }
+
+    #[test]
+    fn rejects_radix() {
+        let all = Radix::all();
+        let parse = |input| parse_radix::<u8>(input, &all, Radix::Decimal, true);
+        assert_eq!(parse(""), Err(RadixError::Empty));
+        assert_eq!(parse("0x"), Err(RadixError::Empty));
+        assert_eq!(parse("_1"), Err(RadixError::MisplacedUnderscore));
+        assert_eq!(parse("1__0"), Err(RadixError::MisplacedUnderscore));
+        assert_eq!(parse("0x1_"), Err(RadixError::MisplacedUnderscore));
+        assert_eq!(
+            parse("0b102"),
+            Err(RadixError::InvalidDigits {
+                radix: Radix::Binary,
+                digits: "102".to_string()
+            })
+        );
+        assert!(matches!(
+            parse("+-1"),
+            Err(RadixError::InvalidDigits { .. })
+        ));
+        assert_eq!(parse("256"), Err(RadixError::OutOfRange));
+        assert_eq!(parse("-1"), Err(RadixError::OutOfRange));
+        assert_eq!(
+            parse_radix::<u8>("0x10", &[Radix::Decimal], Radix::Decimal, true),
+            Err(RadixError::UnsupportedRadix(Radix::Hexadecimal))
+        );
+        assert!(matches!(
+            parse_radix::<u8>("1_0", &all, Radix::Decimal, false),
+            Err(RadixError::InvalidDigits { .. })
+        ));
+    }
+
+    #[test]
+    fn radix_round_trips() {
+        let all = Radix::all();
+        for value in [0_i64, 31, -5, i64::MAX, i64::MIN].iter() {
+            for radix in &all {
+                let formatted = format_radix(*value, *radix);
+                assert_eq!(
+                    parse_radix::<i64>(&formatted, &all, Radix::Decimal, false),
+                    Ok(*value),
+                    "{}",
+                    formatted
+                );
+            }
+        }
+        assert_eq!(format_radix(31_u8, Radix::Hexadecimal), "0x1F");
+        assert_eq!(format_radix(-5_i8, Radix::Binary), "-0b101");
+    }
+}
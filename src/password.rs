@@ -0,0 +1,430 @@
+use std::{collections, error, fmt, fs, io, path};
+
+/// A password that doesn't show its value through `Debug` and has no `Display`, so it can't end
+/// up in logs or error messages by accident. Use `expose` where the value is really needed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: String) -> Secret {
+        Secret(value)
+    }
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret(***)")
+    }
+}
+
+#[derive(Debug)]
+pub enum DenyListError {
+    Io(io::Error),
+}
+
+impl fmt::Display for DenyListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DenyListError::Io(e) => write!(f, "unable to read deny list file: {}", e),
+        }
+    }
+}
+
+impl error::Error for DenyListError {}
+
+impl From<io::Error> for DenyListError {
+    fn from(e: io::Error) -> DenyListError {
+        DenyListError::Io(e)
+    }
+}
+
+/// Common or breached passwords that are never allowed, compared case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct DenyList {
+    passwords: collections::HashSet<String>,
+}
+
+impl DenyList {
+    /// Parses text with one password per line. Blank lines and lines starting with `#` are
+    /// skipped.
+    pub fn parse_deny_list(text: &str) -> DenyList {
+        DenyList {
+            passwords: text
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_lowercase)
+                .collect(),
+        }
+    }
+    pub fn from_file(path: impl AsRef<path::Path>) -> Result<DenyList, DenyListError> {
+        Ok(DenyList::parse_deny_list(&fs::read_to_string(path)?))
+    }
+    pub fn contains(&self, password: &str) -> bool {
+        self.passwords.contains(&password.to_lowercase())
+    }
+    pub fn len(&self) -> usize {
+        self.passwords.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.passwords.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CharClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    /// Anything that isn't a letter or digit, including spaces.
+    Symbol,
+}
+
+impl CharClass {
+    pub fn all() -> Vec<CharClass> {
+        vec![
+            CharClass::Lowercase,
+            CharClass::Uppercase,
+            CharClass::Digit,
+            CharClass::Symbol,
+        ]
+    }
+    pub fn of(c: char) -> CharClass {
+        if c.is_lowercase() {
+            CharClass::Lowercase
+        } else if c.is_uppercase() {
+            CharClass::Uppercase
+        } else if c.is_numeric() {
+            CharClass::Digit
+        } else {
+            CharClass::Symbol
+        }
+    }
+    // the number of characters someone guessing would have to try
+    fn pool_size(&self) -> f64 {
+        match self {
+            CharClass::Lowercase | CharClass::Uppercase => 26.0,
+            CharClass::Digit => 10.0,
+            CharClass::Symbol => 33.0,
+        }
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharClass::Lowercase => write!(f, "lowercase letter"),
+            CharClass::Uppercase => write!(f, "uppercase letter"),
+            CharClass::Digit => write!(f, "digit"),
+            CharClass::Symbol => write!(f, "symbol"),
+        }
+    }
+}
+
+/// The rules a new password must meet. Lengths are in characters.
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    min_length: usize,
+    max_length: Option<usize>,
+    required: Vec<CharClass>,
+    deny_list: DenyList,
+}
+
+impl PasswordPolicy {
+    pub fn new(min_length: usize) -> PasswordPolicy {
+        PasswordPolicy {
+            min_length,
+            max_length: None,
+            required: Vec::new(),
+            deny_list: DenyList::default(),
+        }
+    }
+    pub fn with_max_length(self, max_length: usize) -> PasswordPolicy {
+        PasswordPolicy {
+            max_length: Some(max_length),
+            ..self
+        }
+    }
+    pub fn with_required(self, required: Vec<CharClass>) -> PasswordPolicy {
+        PasswordPolicy { required, ..self }
+    }
+    pub fn with_deny_list(self, deny_list: DenyList) -> PasswordPolicy {
+        PasswordPolicy { deny_list, ..self }
+    }
+    pub fn get_min_length(&self) -> usize {
+        self.min_length
+    }
+    pub fn get_max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+    pub fn get_required(&self) -> &[CharClass] {
+        &self.required
+    }
+    pub fn get_deny_list(&self) -> &DenyList {
+        &self.deny_list
+    }
+    /// Every rule that `password` breaks. The messages never include the password.
+    pub fn check(&self, password: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        let length = password.chars().count();
+        if length < self.min_length {
+            errors.push(format!(
+                "Password should be at least {} characters but was {}",
+                self.min_length, length
+            ));
+        }
+        if let Some(max) = self.max_length.filter(|max| length > *max) {
+            errors.push(format!(
+                "Password should be at most {} characters but was {}",
+                max, length
+            ));
+        }
+        for class in &self.required {
+            if !password.chars().any(|c| CharClass::of(c) == *class) {
+                errors.push(format!("Password should contain at least one {}", class));
+            }
+        }
+        if self.deny_list.contains(password) {
+            errors.push("Password is too common".to_string());
+        }
+        errors
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Strength {
+    VeryWeak,
+    Weak,
+    Fair,
+    Strong,
+    VeryStrong,
+}
+
+impl Strength {
+    /// Estimates how hard `password` is to guess from its length and the kinds of characters
+    /// in it. Repeated characters and runs like `abc` or `321` count for little, and anything on
+    /// the deny list is `VeryWeak`.
+    pub fn estimate(password: &str, deny_list: &DenyList) -> Strength {
+        if deny_list.contains(password) {
+            return Strength::VeryWeak;
+        }
+        let classes = password
+            .chars()
+            .map(CharClass::of)
+            .collect::<collections::BTreeSet<_>>();
+        let pool: f64 = classes.iter().map(CharClass::pool_size).sum();
+        let mut effective_length = 0.0;
+        let mut previous: Option<char> = None;
+        for c in password.chars() {
+            let predictable = matches!(previous, Some(p) if (c as i64 - p as i64).abs() <= 1);
+            effective_length += if predictable { 0.25 } else { 1.0 };
+            previous = Some(c);
+        }
+        let bits = effective_length * pool.max(1.0).log2();
+        if bits < 28.0 {
+            Strength::VeryWeak
+        } else if bits < 36.0 {
+            Strength::Weak
+        } else if bits < 60.0 {
+            Strength::Fair
+        } else if bits < 80.0 {
+            Strength::Strong
+        } else {
+            Strength::VeryStrong
+        }
+    }
+    /// From 0 for `VeryWeak` to 4 for `VeryStrong`, eg for a strength meter.
+    pub fn score(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl fmt::Display for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strength::VeryWeak => write!(f, "Very weak"),
+            Strength::Weak => write!(f, "Weak"),
+            Strength::Fair => write!(f, "Fair"),
+            Strength::Strong => write!(f, "Strong"),
+            Strength::VeryStrong => write!(f, "Very strong"),
+        }
+    }
+}
+
+/// A new password checked against a `PasswordPolicy`. Policy failures are validation errors, all
+/// reported at once. There is no way to set the value, as passwords shouldn't be pre-filled.
+pub struct Password {
+    input: String,
+    policy: PasswordPolicy,
+    validations: crate::Validations<Secret>,
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Password")
+            .field("input", &"***")
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl Password {
+    pub fn new(policy: PasswordPolicy, validations: crate::Validations<Secret>) -> Password {
+        Password {
+            input: String::new(),
+            policy,
+            validations,
+        }
+    }
+    /// The raw input, for rendering it back into the (masked) input element.
+    pub fn expose_input(&self) -> &str {
+        &self.input
+    }
+    pub fn get_policy(&self) -> &PasswordPolicy {
+        &self.policy
+    }
+    pub fn strength(&self) -> Strength {
+        Strength::estimate(&self.input, &self.policy.deny_list)
+    }
+    /// The rules the current input breaks, eg for a checklist shown while typing.
+    pub fn policy_errors(&self) -> Vec<String> {
+        self.policy.check(&self.input)
+    }
+}
+
+impl crate::UserInput for Password {
+    type Output = Secret;
+    type Input = String;
+    fn update(&mut self, input: Self::Input) {
+        self.input = input;
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let errors = self.policy.check(&self.input);
+        if !errors.is_empty() {
+            return Err(crate::Error::Validation(errors.into()));
+        }
+        let parsed = Secret::new(self.input.clone());
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
+/// A new password entered twice.
+pub struct PasswordConfirm {
+    password: Password,
+    confirm: String,
+}
+
+impl fmt::Debug for PasswordConfirm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasswordConfirm")
+            .field("password", &self.password)
+            .field("confirm", &"***")
+            .finish()
+    }
+}
+
+pub enum PasswordConfirmMsg {
+    Password(String),
+    Confirm(String),
+}
+
+impl PasswordConfirm {
+    pub fn new(password: Password) -> PasswordConfirm {
+        PasswordConfirm {
+            password,
+            confirm: String::new(),
+        }
+    }
+    pub fn get_password(&self) -> &Password {
+        &self.password
+    }
+    pub fn expose_confirm(&self) -> &str {
+        &self.confirm
+    }
+    /// Whether the confirmation matches so far, eg to show a tick once something is entered.
+    pub fn matches(&self) -> bool {
+        self.password.input == self.confirm
+    }
+}
+
+impl crate::UserInput for PasswordConfirm {
+    type Output = Secret;
+    type Input = PasswordConfirmMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            PasswordConfirmMsg::Password(input) => self.password.update(input),
+            PasswordConfirmMsg::Confirm(input) => self.confirm = input,
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = self.password.parse()?;
+        if !self.matches() {
+            return Err(crate::Error::Validation(
+                vec!["Passwords don't match".to_string()].into(),
+            ));
+        }
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deny_list() -> DenyList {
+        DenyList::parse_deny_list("# common passwords\n\nPassword\n  letmein  \n")
+    }
+
+    #[test]
+    fn parses_deny_list() {
+        let deny_list = deny_list();
+        assert_eq!(deny_list.len(), 2);
+        assert!(deny_list.contains("password"));
+        assert!(deny_list.contains("LetMeIn"));
+        assert!(!deny_list.contains("# common passwords"));
+        assert!(DenyList::parse_deny_list("\n# only a comment\n").is_empty());
+    }
+
+    #[test]
+    fn checks_policy() {
+        let policy = PasswordPolicy::new(8)
+            .with_max_length(12)
+            .with_required(vec![CharClass::Uppercase, CharClass::Digit])
+            .with_deny_list(deny_list());
+        assert!(policy.check("Tr0ub4dor").is_empty());
+        assert_eq!(
+            policy.check("short"),
+            vec![
+                "Password should be at least 8 characters but was 5",
+                "Password should contain at least one uppercase letter",
+                "Password should contain at least one digit",
+            ]
+        );
+        assert_eq!(
+            policy.check("Tr0ub4dor&3xyz"),
+            vec!["Password should be at most 12 characters but was 14"]
+        );
+        // lengths are in characters rather than bytes
+        assert!(policy.check("Pässwörd1").is_empty());
+        let policy = PasswordPolicy::new(1).with_deny_list(deny_list());
+        assert_eq!(policy.check("PASSWORD"), vec!["Password is too common"]);
+    }
+
+    #[test]
+    fn estimates_strength() {
+        let deny_list = deny_list();
+        let strength = |password| Strength::estimate(password, &deny_list);
+        assert_eq!(strength(""), Strength::VeryWeak);
+        assert_eq!(strength("abcdefghijkl"), Strength::VeryWeak);
+        assert_eq!(strength("letmein"), Strength::VeryWeak);
+        assert_eq!(strength("qzmxkwp"), Strength::Weak);
+        assert_eq!(strength("qzmxkwpt"), Strength::Fair);
+        assert_eq!(strength("qZ3mX8kW1pT"), Strength::Strong);
+        assert_eq!(strength("qZ3mX8kW1pT7vR"), Strength::VeryStrong);
+        assert_eq!(Strength::VeryStrong.score(), 4);
+    }
+}
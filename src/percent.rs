@@ -0,0 +1,237 @@
+use std::{error, fmt};
+
+#[derive(Debug)]
+pub struct PercentError {
+    input: String,
+}
+
+impl PercentError {
+    pub fn new(input: String) -> PercentError {
+        PercentError { input }
+    }
+}
+
+impl fmt::Display for PercentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is not a valid percentage, expected eg 12.5% or 0.125",
+            self.input
+        )
+    }
+}
+
+impl error::Error for PercentError {}
+
+/// How a number typed without a `%` sign is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BareNumber {
+    /// `0.125` is 12.5%
+    Fraction,
+    /// `12.5` is 12.5%
+    Percentage,
+}
+
+fn hundred() -> rust_decimal::Decimal {
+    rust_decimal::Decimal::new(100, 0)
+}
+
+/// Formats a fraction as a percentage, eg `0.125` as `12.5%`. Fractions too large to be
+/// expressed as a percentage are formatted as they are, without the `%`.
+pub fn format_percent(fraction: &rust_decimal::Decimal) -> String {
+    match fraction.checked_mul(hundred()) {
+        Some(percentage) => format!("{}%", percentage.normalize()),
+        None => fraction.normalize().to_string(),
+    }
+}
+
+// `None` if the sum overflows
+fn checked_sum<'a>(
+    fractions: impl IntoIterator<Item = &'a rust_decimal::Decimal>,
+) -> Option<rust_decimal::Decimal> {
+    fractions
+        .into_iter()
+        .try_fold(rust_decimal::Decimal::new(0, 0), |total, fraction| {
+            total.checked_add(*fraction)
+        })
+}
+
+pub fn parse_percent(input: &str, bare: BareNumber) -> Result<rust_decimal::Decimal, PercentError> {
+    let trimmed = input.trim();
+    let err = || PercentError::new(input.to_string());
+    match trimmed.strip_suffix('%') {
+        Some(percentage) => percentage
+            .trim_end()
+            .parse::<rust_decimal::Decimal>()
+            .map(|percentage| percentage / hundred())
+            .map_err(|_| err()),
+        None => {
+            let number = trimmed
+                .parse::<rust_decimal::Decimal>()
+                .map_err(|_| err())?;
+            Ok(match bare {
+                BareNumber::Fraction => number,
+                BareNumber::Percentage => number / hundred(),
+            })
+        }
+    }
+}
+
+/// Validation for percentages between 0% and 100% inclusive.
+pub fn zero_to_hundred(fraction: &rust_decimal::Decimal) -> crate::ValidationResult {
+    if fraction.is_sign_negative() || *fraction > rust_decimal::Decimal::new(1, 0) {
+        Err(format!(
+            "Percentage should be between 0% and 100% but was {}",
+            format_percent(fraction)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validation for allocations that must add up to exactly 100%.
+#[allow(clippy::ptr_arg)]
+pub fn sums_to_hundred(fractions: &Vec<rust_decimal::Decimal>) -> crate::ValidationResult {
+    let one = rust_decimal::Decimal::new(1, 0);
+    let total = checked_sum(fractions)
+        .ok_or_else(|| "Allocations should sum to 100% but are too large to add up".to_string())?;
+    if total == one {
+        return Ok(());
+    }
+    let sum = format!(
+        "Allocations should sum to 100% but sum to {}",
+        format_percent(&total)
+    );
+    Err(match one.checked_sub(total) {
+        Some(remaining) => format!("{} ({} remaining)", sum, format_percent(&remaining)),
+        None => sum,
+    })
+}
+
+/// A percentage typed either with a `%` sign or as a bare number, parsed to a fraction (so 12.5%
+/// is `0.125`).
+pub struct Percent {
+    input: String,
+    bare: BareNumber,
+    min: Option<rust_decimal::Decimal>,
+    max: Option<rust_decimal::Decimal>,
+    validations: crate::Validations<rust_decimal::Decimal>,
+}
+
+impl Percent {
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+    /// `min` and `max` are fractions, like the output.
+    pub fn new(
+        data: rust_decimal::Decimal,
+        bare: BareNumber,
+        min: Option<rust_decimal::Decimal>,
+        max: Option<rust_decimal::Decimal>,
+        validations: crate::Validations<rust_decimal::Decimal>,
+    ) -> Percent {
+        Percent {
+            input: format_percent(&data),
+            bare,
+            min,
+            max,
+            validations,
+        }
+    }
+    pub fn get_min(&self) -> Option<rust_decimal::Decimal> {
+        self.min
+    }
+    pub fn get_max(&self) -> Option<rust_decimal::Decimal> {
+        self.max
+    }
+}
+
+impl Default for Percent {
+    fn default() -> Percent {
+        Percent::new(
+            rust_decimal::Decimal::new(0, 0),
+            BareNumber::Percentage,
+            None,
+            None,
+            crate::Validations::new(),
+        )
+    }
+}
+
+impl crate::SetInput for Percent {
+    fn set(&mut self, data: rust_decimal::Decimal) {
+        self.input = format_percent(&data);
+    }
+}
+
+impl crate::UserInput for Percent {
+    type Output = rust_decimal::Decimal;
+    type Input = String;
+    fn update(&mut self, input: Self::Input) {
+        self.input = input;
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = parse_percent(&self.input, self.bare)?;
+        crate::check_bounds(
+            "Percentage",
+            ("at least", "at most"),
+            &parsed,
+            (self.min.as_ref(), self.max.as_ref()),
+            format_percent,
+        )
+        .map_err(|e| crate::Error::Validation(vec![e].into()))?;
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
+/// A list of percentages that must sum to 100%, eg splitting a cost between departments.
+pub struct Allocation {
+    shares: Vec<Percent>,
+}
+
+pub enum AllocationMsg {
+    Share(usize, String),
+}
+
+impl Allocation {
+    pub fn new(shares: Vec<Percent>) -> Allocation {
+        Allocation { shares }
+    }
+    pub fn get_shares(&self) -> &[Percent] {
+        &self.shares
+    }
+    /// The percentage still to be allocated, if all the shares parse and their total fits in a
+    /// `Decimal`.
+    pub fn remaining(&self) -> Option<rust_decimal::Decimal> {
+        let shares = self
+            .shares
+            .iter()
+            .map(|share| crate::UserInput::parse(share).ok())
+            .collect::<Option<Vec<_>>>()?;
+        rust_decimal::Decimal::new(1, 0).checked_sub(checked_sum(&shares)?)
+    }
+}
+
+impl crate::UserInput for Allocation {
+    type Output = Vec<rust_decimal::Decimal>;
+    type Input = AllocationMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            AllocationMsg::Share(idx, input) => {
+                if let Some(share) = self.shares.get_mut(idx) {
+                    share.update(input);
+                }
+            }
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = self
+            .shares
+            .iter()
+            .map(|share| share.parse())
+            .collect::<crate::Result<Vec<_>>>()?;
+        sums_to_hundred(&parsed).map_err(|e| crate::Error::Validation(vec![e].into()))?;
+        Ok(parsed)
+    }
+}
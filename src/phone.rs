@@ -0,0 +1,506 @@
+use crate::country::{Country, CountryError};
+use crate::inputs;
+use std::{collections, error, fmt, str};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PhoneError {
+    Empty,
+    /// There is no `PhoneInfo` for the country.
+    UnsupportedCountry(Country),
+    InvalidCharacter(char),
+    /// The number was entered with a `+` or `00` prefix for a different country.
+    CallingCodeMismatch {
+        country: Country,
+        calling_code: &'static str,
+        entered: String,
+    },
+    TooShort {
+        country: Country,
+        min: usize,
+        actual: usize,
+    },
+    TooLong {
+        country: Country,
+        max: usize,
+        actual: usize,
+    },
+}
+
+impl fmt::Display for PhoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PhoneError::Empty => write!(f, "no phone number was entered"),
+            PhoneError::UnsupportedCountry(country) => {
+                write!(f, "phone numbers in {} are not supported", country)
+            }
+            PhoneError::InvalidCharacter(c) => {
+                write!(f, "'{}' is not allowed in a phone number", c)
+            }
+            PhoneError::CallingCodeMismatch {
+                country,
+                calling_code,
+                entered,
+            } => write!(
+                f,
+                "{} does not start with +{}, the calling code for {}",
+                entered, calling_code, country
+            ),
+            PhoneError::TooShort {
+                country,
+                min,
+                actual,
+            } => write!(
+                f,
+                "{} numbers have at least {} digits but this had {}",
+                country, min, actual
+            ),
+            PhoneError::TooLong {
+                country,
+                max,
+                actual,
+            } => write!(
+                f,
+                "{} numbers have at most {} digits but this had {}",
+                country, max, actual
+            ),
+        }
+    }
+}
+
+impl error::Error for PhoneError {}
+
+/// The phone number rules for a country.
+#[derive(Debug)]
+pub struct PhoneInfo {
+    code: &'static str,
+    calling_code: &'static str,
+    trunk_prefix: &'static str,
+    min_digits: usize,
+    max_digits: usize,
+    // `(leading digits, pattern)` with `X` for each digit and without the trunk prefix. The first
+    // pattern whose leading digits match and whose length fits the number is used.
+    patterns: &'static [(&'static str, &'static str)],
+}
+
+macro_rules! country {
+    // one pattern for every number in the country
+    ($code:expr, $calling:expr, $trunk:expr, $min:expr, $max:expr, $pattern:literal) => {
+        country!($code, $calling, $trunk, $min, $max, &[("", $pattern)])
+    };
+    ($code:expr, $calling:expr, $trunk:expr, $min:expr, $max:expr, $patterns:expr) => {
+        PhoneInfo {
+            code: $code,
+            calling_code: $calling,
+            trunk_prefix: $trunk,
+            min_digits: $min,
+            max_digits: $max,
+            patterns: $patterns,
+        }
+    };
+}
+
+// mobiles, then landlines by area code length, eg 020 7946 0000 in London and 0113 496 0000 in
+// Leeds
+const GB_PATTERNS: &[(&str, &str)] = &[
+    ("7", "XXXX XXXXXX"),
+    ("2", "XX XXXX XXXX"),
+    ("11", "XXX XXX XXXX"),
+    ("121", "XXX XXX XXXX"),
+    ("131", "XXX XXX XXXX"),
+    ("141", "XXX XXX XXXX"),
+    ("151", "XXX XXX XXXX"),
+    ("161", "XXX XXX XXXX"),
+    ("191", "XXX XXX XXXX"),
+    ("3", "XXX XXX XXXX"),
+    ("8", "XXX XXX XXXX"),
+    ("", "XXXX XXXXXX"),
+];
+
+// mobiles, then landlines
+const AU_PATTERNS: &[(&str, &str)] = &[("4", "XXX XXX XXX"), ("", "X XXXX XXXX")];
+
+// sorted by code. Digit counts are for the national significant number, ie without the calling
+// code or trunk prefix.
+const PHONE_INFO: &[PhoneInfo] = &[
+    country!("AU", "61", "0", 9, 9, AU_PATTERNS),
+    country!("BR", "55", "0", 10, 11, "XX XXXXX-XXXX"),
+    country!("CA", "1", "", 10, 10, "(XXX) XXX-XXXX"),
+    country!("CN", "86", "0", 11, 11, "XXX XXXX XXXX"),
+    country!("DE", "49", "0", 6, 13, "XXX XXXXXXXX"),
+    country!("ES", "34", "", 9, 9, "XXX XX XX XX"),
+    country!("FR", "33", "0", 9, 9, "X XX XX XX XX"),
+    country!("GB", "44", "0", 10, 10, GB_PATTERNS),
+    country!("IE", "353", "0", 7, 9, "XX XXX XXXX"),
+    country!("IN", "91", "0", 10, 10, "XXXXX XXXXX"),
+    country!("IT", "39", "", 6, 11, "XXX XXX XXXX"),
+    country!("JP", "81", "0", 9, 10, "XX XXXX XXXX"),
+    country!("MX", "52", "", 10, 10, "XX XXXX XXXX"),
+    country!("NL", "31", "0", 9, 9, "X XXXXXXXX"),
+    country!("NZ", "64", "0", 8, 10, "XX XXX XXXX"),
+    country!("SG", "65", "", 8, 8, "XXXX XXXX"),
+    country!("US", "1", "", 10, 10, "(XXX) XXX-XXXX"),
+    country!("ZA", "27", "0", 9, 9, "XX XXX XXXX"),
+];
+
+impl PhoneInfo {
+    /// `None` if there are no phone number rules for `country`.
+    pub fn of(country: Country) -> Option<&'static PhoneInfo> {
+        PHONE_INFO.iter().find(|info| info.code == country.code())
+    }
+    /// The countries with phone number rules, eg for the options of a `Phone`.
+    pub fn countries() -> collections::BTreeSet<Country> {
+        Country::all()
+            .into_iter()
+            .filter(|country| PhoneInfo::of(*country).is_some())
+            .collect()
+    }
+    /// The international calling code without the `+`, eg `44`.
+    pub fn calling_code(&self) -> &'static str {
+        self.calling_code
+    }
+    /// The prefix for dialling within the country, eg `0` in the UK.
+    pub fn trunk_prefix(&self) -> &'static str {
+        self.trunk_prefix
+    }
+    pub fn min_digits(&self) -> usize {
+        self.min_digits
+    }
+    pub fn max_digits(&self) -> usize {
+        self.max_digits
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhoneFormat {
+    /// eg `+447911123456`
+    E164,
+    /// eg `07911 123456`, or `020 7946 0000` for a London landline
+    National,
+    /// eg `+44 7911 123456`
+    International,
+}
+
+/// A phone number in a country, stored as its national significant number.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PhoneNumber {
+    country: Country,
+    national: String,
+}
+
+impl PhoneNumber {
+    /// Parses `input` as a number in `country`. The number may be entered nationally (with or
+    /// without the trunk prefix) or internationally with a `+` or `00` prefix, and may contain
+    /// spaces, dots, hyphens and parentheses.
+    pub fn parse(country: Country, input: &str) -> Result<PhoneNumber, PhoneError> {
+        let input = input.trim();
+        if let Some(c) = input
+            .chars()
+            .find(|c| !c.is_ascii_digit() && !" .-()+".contains(*c))
+        {
+            return Err(PhoneError::InvalidCharacter(c));
+        }
+        if input.chars().skip(1).any(|c| c == '+') {
+            return Err(PhoneError::InvalidCharacter('+'));
+        }
+        let digits: String = input.chars().filter(|c| c.is_ascii_digit()).collect();
+        if digits.is_empty() {
+            return Err(PhoneError::Empty);
+        }
+        let international = if input.starts_with('+') {
+            Some(digits.as_str())
+        } else {
+            digits.strip_prefix("00")
+        };
+        let info = PhoneInfo::of(country).ok_or(PhoneError::UnsupportedCountry(country))?;
+        let in_range =
+            |digits: &str| digits.len() >= info.min_digits && digits.len() <= info.max_digits;
+        let national = match international {
+            Some(international) => {
+                let rest = international
+                    .strip_prefix(info.calling_code)
+                    .ok_or_else(|| PhoneError::CallingCodeMismatch {
+                        country,
+                        calling_code: info.calling_code,
+                        entered: input.to_string(),
+                    })?;
+                // eg `+44 (0)20 7946 0000`
+                rest.strip_prefix(info.trunk_prefix)
+                    .filter(|national| {
+                        !info.trunk_prefix.is_empty() && !in_range(rest) && in_range(national)
+                    })
+                    .unwrap_or(rest)
+            }
+            None => {
+                // also accept the calling code without a `+`, eg `1 201 555 0123` in the US
+                let without_code = digits
+                    .strip_prefix(info.calling_code)
+                    .filter(|rest| !in_range(&digits) && in_range(rest));
+                let without_trunk = digits
+                    .strip_prefix(info.trunk_prefix)
+                    .filter(|_| !info.trunk_prefix.is_empty());
+                without_code.or(without_trunk).unwrap_or(&digits)
+            }
+        };
+        if national.len() < info.min_digits {
+            return Err(PhoneError::TooShort {
+                country,
+                min: info.min_digits,
+                actual: national.len(),
+            });
+        }
+        if national.len() > info.max_digits {
+            return Err(PhoneError::TooLong {
+                country,
+                max: info.max_digits,
+                actual: national.len(),
+            });
+        }
+        Ok(PhoneNumber {
+            country,
+            national: national.to_string(),
+        })
+    }
+    pub fn country(&self) -> Country {
+        self.country
+    }
+    fn info(&self) -> &'static PhoneInfo {
+        PhoneInfo::of(self.country).expect("numbers are only parsed for countries in the table")
+    }
+    /// The national significant number, ie without the calling code or trunk prefix.
+    pub fn national_number(&self) -> &str {
+        &self.national
+    }
+    pub fn e164(&self) -> String {
+        format!("+{}{}", self.info().calling_code, self.national)
+    }
+    pub fn format(&self, format: PhoneFormat) -> String {
+        match format {
+            PhoneFormat::E164 => self.e164(),
+            PhoneFormat::National => format!(
+                "{}{}",
+                self.info().trunk_prefix,
+                self.pattern()
+                    .map(|pattern| self.grouped(pattern))
+                    .unwrap_or_else(|| self.national.clone())
+            ),
+            PhoneFormat::International => {
+                // international style only separates groups with spaces
+                let grouped = self
+                    .pattern()
+                    .map(|pattern| {
+                        let pattern: String = pattern
+                            .chars()
+                            .filter(|c| *c != '(' && *c != ')')
+                            .map(|c| if c == 'X' { c } else { ' ' })
+                            .collect();
+                        self.grouped(&pattern)
+                    })
+                    .unwrap_or_else(|| self.national.clone());
+                format!("+{} {}", self.info().calling_code, grouped)
+            }
+        }
+    }
+    // numbers without a pattern for their leading digits and length are left ungrouped
+    fn pattern(&self) -> Option<&'static str> {
+        self.info()
+            .patterns
+            .iter()
+            .find(|(leading, pattern)| {
+                self.national.starts_with(leading)
+                    && pattern.chars().filter(|c| *c == 'X').count() == self.national.len()
+            })
+            .map(|(_, pattern)| *pattern)
+    }
+    fn grouped(&self, pattern: &str) -> String {
+        let mut digits = self.national.chars();
+        pattern
+            .chars()
+            .map(|c| match c {
+                'X' => digits.next().unwrap_or(c),
+                c => c,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.e164())
+    }
+}
+
+/// A country and phone number. `PhoneMsg::Normalise` rewrites the number in national format
+/// (eg on blur), and setting a value writes it in national format.
+pub struct Phone {
+    country: inputs::Select<CountryError, Country>,
+    number: inputs::Text,
+    validations: crate::Validations<PhoneNumber>,
+}
+
+pub enum PhoneMsg {
+    Country(String),
+    Number(String),
+    Normalise,
+}
+
+impl Phone {
+    pub fn new(
+        data: PhoneNumber,
+        countries: collections::BTreeSet<Country>,
+        validations: crate::Validations<PhoneNumber>,
+    ) -> Phone {
+        Phone {
+            country: inputs::Select::new(data.country, countries),
+            number: inputs::Text::new(
+                &data.format(PhoneFormat::National),
+                crate::Validations::new(),
+            ),
+            validations,
+        }
+    }
+    pub fn get_country(&self) -> &inputs::Select<CountryError, Country> {
+        &self.country
+    }
+    pub fn get_number(&self) -> &inputs::Text {
+        &self.number
+    }
+}
+
+impl crate::SetInput for Phone {
+    fn set(&mut self, data: PhoneNumber) {
+        self.country.set(data.country);
+        self.number.set(data.format(PhoneFormat::National));
+    }
+}
+
+impl crate::UserInput for Phone {
+    type Output = PhoneNumber;
+    type Input = PhoneMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            PhoneMsg::Country(input) => self.country.update(input),
+            PhoneMsg::Number(input) => self.number.update(input),
+            PhoneMsg::Normalise => {
+                if let Ok(parsed) = self.parse() {
+                    self.number.set(parsed.format(PhoneFormat::National));
+                }
+            }
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let country = self.country.parse()?;
+        let parsed = PhoneNumber::parse(country, self.number.get_input())?;
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UserInput;
+
+    fn country(code: &str) -> Country {
+        code.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_formats() {
+        let number = PhoneNumber::parse(country("GB"), "07911 123456").unwrap();
+        assert_eq!(number.national_number(), "7911123456");
+        assert_eq!(number.format(PhoneFormat::E164), "+447911123456");
+        assert_eq!(number.format(PhoneFormat::National), "07911 123456");
+        assert_eq!(number.format(PhoneFormat::International), "+44 7911 123456");
+        let number = PhoneNumber::parse(country("GB"), "+44 (0)20 7946 0000").unwrap();
+        assert_eq!(number.format(PhoneFormat::National), "020 7946 0000");
+        let number = PhoneNumber::parse(country("US"), "1 201 555 0123").unwrap();
+        assert_eq!(number.format(PhoneFormat::National), "(201) 555-0123");
+        assert_eq!(number.format(PhoneFormat::International), "+1 201 555 0123");
+    }
+
+    #[test]
+    fn round_trips() {
+        for (code, input) in [
+            ("GB", "0113 496 0000"),
+            ("AU", "0412 345 678"),
+            ("AU", "02 9876 5432"),
+            ("FR", "01 23 45 67 89"),
+            ("DE", "030 123456"),
+        ]
+        .iter()
+        {
+            let number = PhoneNumber::parse(country(code), input).unwrap();
+            for format in [
+                PhoneFormat::E164,
+                PhoneFormat::National,
+                PhoneFormat::International,
+            ]
+            .iter()
+            {
+                let formatted = number.format(*format);
+                assert_eq!(
+                    PhoneNumber::parse(country(code), &formatted).unwrap(),
+                    number,
+                    "{}",
+                    formatted
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects() {
+        let gb = country("GB");
+        for (input, error) in [
+            ("", PhoneError::Empty),
+            ("()", PhoneError::Empty),
+            ("07911 12345a", PhoneError::InvalidCharacter('a')),
+            ("0791+1123456", PhoneError::InvalidCharacter('+')),
+            (
+                "+33 1 23 45 67 89",
+                PhoneError::CallingCodeMismatch {
+                    country: gb,
+                    calling_code: "44",
+                    entered: "+33 1 23 45 67 89".to_string(),
+                },
+            ),
+            (
+                "0791112",
+                PhoneError::TooShort {
+                    country: gb,
+                    min: 10,
+                    actual: 6,
+                },
+            ),
+            (
+                "079111234567",
+                PhoneError::TooLong {
+                    country: gb,
+                    max: 10,
+                    actual: 11,
+                },
+            ),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                &PhoneNumber::parse(gb, input).unwrap_err(),
+                error,
+                "{}",
+                input
+            );
+        }
+        assert!("XX".parse::<Country>().is_err());
+    }
+
+    #[test]
+    fn normalises_input() {
+        let number = PhoneNumber::parse(country("GB"), "07911 123456").unwrap();
+        let mut phone = Phone::new(number, PhoneInfo::countries(), crate::Validations::new());
+        phone.update(PhoneMsg::Country("us".to_string()));
+        phone.update(PhoneMsg::Number("+1.201.555.0123".to_string()));
+        phone.update(PhoneMsg::Normalise);
+        assert_eq!(phone.get_number().get_input(), "(201) 555-0123");
+        assert_eq!(phone.parse().unwrap().e164(), "+12015550123");
+    }
+}
@@ -0,0 +1,457 @@
+use std::{error, fmt};
+
+#[derive(Debug)]
+pub struct QuantityError {
+    input: String,
+    reason: String,
+}
+
+impl QuantityError {
+    pub fn new(input: String, reason: String) -> QuantityError {
+        QuantityError { input, reason }
+    }
+}
+
+impl fmt::Display for QuantityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not a valid quantity: {}", self.input, self.reason)
+    }
+}
+
+impl error::Error for QuantityError {}
+
+#[derive(Debug)]
+pub struct UnitTableError {
+    symbol: String,
+    factor: rust_decimal::Decimal,
+}
+
+impl UnitTableError {
+    pub fn new(symbol: String, factor: rust_decimal::Decimal) -> UnitTableError {
+        UnitTableError { symbol, factor }
+    }
+}
+
+impl fmt::Display for UnitTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unit {} has a factor of {} but it should be greater than zero",
+            self.symbol, self.factor
+        )
+    }
+}
+
+impl error::Error for UnitTableError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    Mass,
+    Length,
+    Volume,
+    Other(&'static str),
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dimension::Mass => write!(f, "mass"),
+            Dimension::Length => write!(f, "length"),
+            Dimension::Volume => write!(f, "volume"),
+            Dimension::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// A unit of measure. `factor` converts an amount in this unit to the canonical unit of its
+/// dimension, which is the unit in the table with a factor of one.
+///
+/// `symbols` are matched exactly, as case matters for them (mg vs Mg), and the first is the one
+/// displayed. `names` are spelled out and matched ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unit {
+    symbol: String,
+    aliases: Vec<String>,
+    names: Vec<String>,
+    dimension: Dimension,
+    factor: rust_decimal::Decimal,
+}
+
+impl Unit {
+    pub fn new(
+        symbols: &[&str],
+        names: &[&str],
+        dimension: Dimension,
+        factor: rust_decimal::Decimal,
+    ) -> Unit {
+        let (symbol, aliases) = symbols
+            .split_first()
+            .expect("units have at least one symbol");
+        Unit {
+            symbol: symbol.to_string(),
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            names: names.iter().map(|name| name.to_string()).collect(),
+            dimension,
+            factor,
+        }
+    }
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+    pub fn factor(&self) -> rust_decimal::Decimal {
+        self.factor
+    }
+    fn is_named(&self, name: &str) -> bool {
+        self.symbol == name
+            || self.aliases.iter().any(|alias| alias == name)
+            || self
+                .names
+                .iter()
+                .any(|unit_name| unit_name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitTable {
+    units: Vec<Unit>,
+}
+
+impl Default for UnitTable {
+    /// Common metric, imperial and US customary units of mass, length and volume, with
+    /// kilograms, metres and litres as the canonical units.
+    fn default() -> UnitTable {
+        let unit = |symbols, names, dimension, factor: &str| {
+            Unit::new(
+                symbols,
+                names,
+                dimension,
+                factor.parse().expect("unit factors are valid decimals"),
+            )
+        };
+        UnitTable::new(vec![
+            unit(
+                &["mg"],
+                &["milligram", "milligrams"],
+                Dimension::Mass,
+                "0.000001",
+            ),
+            unit(&["g"], &["gram", "grams"], Dimension::Mass, "0.001"),
+            unit(
+                &["kg", "kgs"],
+                &["kilogram", "kilograms"],
+                Dimension::Mass,
+                "1",
+            ),
+            unit(&["t"], &["tonne", "tonnes"], Dimension::Mass, "1000"),
+            unit(
+                &["oz"],
+                &["ounce", "ounces"],
+                Dimension::Mass,
+                "0.028349523125",
+            ),
+            unit(
+                &["lb", "lbs"],
+                &["pound", "pounds"],
+                Dimension::Mass,
+                "0.45359237",
+            ),
+            unit(
+                &["mm"],
+                &["millimetre", "millimetres"],
+                Dimension::Length,
+                "0.001",
+            ),
+            unit(
+                &["cm"],
+                &["centimetre", "centimetres"],
+                Dimension::Length,
+                "0.01",
+            ),
+            unit(&["m"], &["metre", "metres"], Dimension::Length, "1"),
+            unit(
+                &["km"],
+                &["kilometre", "kilometres"],
+                Dimension::Length,
+                "1000",
+            ),
+            unit(&["in"], &["inch", "inches"], Dimension::Length, "0.0254"),
+            unit(&["ft"], &["foot", "feet"], Dimension::Length, "0.3048"),
+            unit(&["yd"], &["yard", "yards"], Dimension::Length, "0.9144"),
+            unit(&["mi"], &["mile", "miles"], Dimension::Length, "1609.344"),
+            unit(
+                &["ml", "mL"],
+                &["millilitre", "millilitres"],
+                Dimension::Volume,
+                "0.001",
+            ),
+            unit(
+                &["cl", "cL"],
+                &["centilitre", "centilitres"],
+                Dimension::Volume,
+                "0.01",
+            ),
+            unit(&["l", "L"], &["litre", "litres"], Dimension::Volume, "1"),
+            unit(
+                &["m3", "m³"],
+                &["cubic metre", "cubic metres"],
+                Dimension::Volume,
+                "1000",
+            ),
+            unit(
+                &["fl oz"],
+                &["fluid ounce", "fluid ounces"],
+                Dimension::Volume,
+                "0.0295735295625",
+            ),
+            unit(
+                &["pt"],
+                &["pint", "pints"],
+                Dimension::Volume,
+                "0.473176473",
+            ),
+            unit(
+                &["gal"],
+                &["gallon", "gallons"],
+                Dimension::Volume,
+                "3.785411784",
+            ),
+        ])
+        .expect("the default units have positive factors")
+    }
+}
+
+impl UnitTable {
+    /// Fails if a unit's factor isn't greater than zero, as amounts couldn't be converted out of
+    /// it.
+    pub fn new(units: Vec<Unit>) -> Result<UnitTable, UnitTableError> {
+        units
+            .into_iter()
+            .try_fold(UnitTable { units: Vec::new() }, UnitTable::with_unit)
+    }
+    pub fn with_unit(mut self, unit: Unit) -> Result<UnitTable, UnitTableError> {
+        if unit.factor <= rust_decimal::Decimal::new(0, 0) {
+            return Err(UnitTableError::new(unit.symbol, unit.factor));
+        }
+        self.units.push(unit);
+        Ok(self)
+    }
+    pub fn get_units(&self) -> &[Unit] {
+        &self.units
+    }
+    pub fn find(&self, name: &str) -> Option<&Unit> {
+        self.units.iter().find(|unit| unit.is_named(name))
+    }
+    pub fn canonical(&self, dimension: Dimension) -> Option<&Unit> {
+        self.units.iter().find(|unit| {
+            unit.dimension == dimension && unit.factor == rust_decimal::Decimal::new(1, 0)
+        })
+    }
+    /// Converts `amount` between two units of the same dimension.
+    pub fn convert(
+        &self,
+        amount: rust_decimal::Decimal,
+        from: &Unit,
+        to: &Unit,
+    ) -> Option<rust_decimal::Decimal> {
+        if from.dimension != to.dimension {
+            return None;
+        }
+        amount
+            .checked_mul(from.factor)?
+            .checked_div(to.factor)
+            .map(|amount| amount.normalize())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuantityValue {
+    amount: rust_decimal::Decimal,
+    unit: String,
+}
+
+impl QuantityValue {
+    pub fn new(amount: rust_decimal::Decimal, unit: &str) -> QuantityValue {
+        QuantityValue {
+            amount,
+            unit: unit.to_string(),
+        }
+    }
+    pub fn amount(&self) -> rust_decimal::Decimal {
+        self.amount
+    }
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
+
+impl fmt::Display for QuantityValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.unit)
+    }
+}
+
+/// An amount with a unit typed together, eg `2500 g`. The unit must be one of the table's units
+/// of `dimension`, and the parsed value is converted to the dimension's canonical unit.
+pub struct Quantity {
+    input: String,
+    table: UnitTable,
+    dimension: Dimension,
+    validations: crate::Validations<QuantityValue>,
+}
+
+impl Quantity {
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+    pub fn new(
+        data: QuantityValue,
+        table: UnitTable,
+        dimension: Dimension,
+        validations: crate::Validations<QuantityValue>,
+    ) -> Quantity {
+        Quantity {
+            input: data.to_string(),
+            table,
+            dimension,
+            validations,
+        }
+    }
+    pub fn get_table(&self) -> &UnitTable {
+        &self.table
+    }
+    pub fn get_dimension(&self) -> Dimension {
+        self.dimension
+    }
+    /// The units that can be typed, eg for a hint or unit picker.
+    pub fn units(&self) -> impl Iterator<Item = &Unit> {
+        let dimension = self.dimension;
+        self.table
+            .units
+            .iter()
+            .filter(move |unit| unit.dimension == dimension)
+    }
+    fn parse_quantity(&self) -> Result<QuantityValue, QuantityError> {
+        let err = |reason: String| QuantityError::new(self.input.clone(), reason);
+        let trimmed = self.input.trim();
+        let amount_len = trimmed
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+            .unwrap_or(trimmed.len());
+        let (amount, unit) = trimmed.split_at(amount_len);
+        if amount.is_empty() {
+            return Err(err("it should start with a number".to_string()));
+        }
+        let amount = amount
+            .parse::<rust_decimal::Decimal>()
+            .map_err(|_| err(format!("{} is not a number", amount)))?;
+        let unit = unit.trim();
+        if unit.is_empty() {
+            return Err(err("a unit is required".to_string()));
+        }
+        let unit = self
+            .table
+            .find(unit)
+            .ok_or_else(|| err(format!("{} is not a known unit", unit)))?;
+        if unit.dimension != self.dimension {
+            return Err(err(format!(
+                "{} is a unit of {} but a {} was expected",
+                unit.symbol, unit.dimension, self.dimension
+            )));
+        }
+        let canonical = self
+            .table
+            .canonical(self.dimension)
+            .ok_or_else(|| err(format!("there is no canonical unit of {}", self.dimension)))?;
+        let amount = self
+            .table
+            .convert(amount, unit, canonical)
+            .ok_or_else(|| err("it is too large to convert".to_string()))?;
+        Ok(QuantityValue::new(amount, &canonical.symbol))
+    }
+}
+
+impl crate::SetInput for Quantity {
+    fn set(&mut self, data: QuantityValue) {
+        self.input = data.to_string();
+    }
+}
+
+impl crate::UserInput for Quantity {
+    type Output = QuantityValue;
+    type Input = String;
+    fn update(&mut self, input: Self::Input) {
+        self.input = input;
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = self.parse_quantity()?;
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quantity(input: &str, dimension: Dimension) -> Result<QuantityValue, QuantityError> {
+        let mut quantity = Quantity::new(
+            QuantityValue::new(rust_decimal::Decimal::new(0, 0), "kg"),
+            UnitTable::default(),
+            dimension,
+            crate::Validations::new(),
+        );
+        crate::UserInput::update(&mut quantity, input.to_string());
+        quantity.parse_quantity()
+    }
+
+    #[test]
+    fn converts() {
+        let value = quantity("2500 g", Dimension::Mass).unwrap();
+        assert_eq!(value.to_string(), "2.5 kg");
+        let value = quantity("250 Millilitres", Dimension::Volume).unwrap();
+        assert_eq!(value.to_string(), "0.25 l");
+        let value = quantity("3 L", Dimension::Volume).unwrap();
+        assert_eq!(value.to_string(), "3 l");
+    }
+
+    #[test]
+    fn symbols_are_case_sensitive() {
+        assert!(quantity("5 mL", Dimension::Volume).is_ok());
+        assert!(quantity("5 ML", Dimension::Volume).is_err());
+        assert!(quantity("5 Mg", Dimension::Mass).is_err());
+        assert!(quantity("5 KG", Dimension::Mass).is_err());
+    }
+
+    #[test]
+    fn rejects() {
+        for (input, dimension) in [
+            ("", Dimension::Mass),
+            ("kg", Dimension::Mass),
+            ("5", Dimension::Mass),
+            ("5 parsecs", Dimension::Length),
+            ("5 m", Dimension::Mass),
+            ("1.2.3 kg", Dimension::Mass),
+        ]
+        .iter()
+        {
+            assert!(quantity(input, *dimension).is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn rejects_zero_factors() {
+        let zero = Unit::new(
+            &["zz"],
+            &[],
+            Dimension::Mass,
+            rust_decimal::Decimal::new(0, 0),
+        );
+        let error = UnitTable::default().with_unit(zero).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unit zz has a factor of 0 but it should be greater than zero"
+        );
+    }
+}
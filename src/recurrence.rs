@@ -0,0 +1,671 @@
+use crate::inputs;
+use chrono::Datelike;
+use std::{collections, error, fmt, str};
+
+#[derive(Debug)]
+pub struct RecurrenceError {
+    reason: String,
+}
+
+impl RecurrenceError {
+    pub fn new(reason: String) -> RecurrenceError {
+        RecurrenceError { reason }
+    }
+}
+
+impl fmt::Display for RecurrenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl error::Error for RecurrenceError {}
+
+// these impl Display and FromStr (round tripping) so they can be used in a `Select`
+macro_rules! select_enum {
+    ($name:ident { $($variant:ident => $label:expr),* $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum $name {
+            $($variant),*
+        }
+
+        impl $name {
+            pub fn all() -> collections::BTreeSet<$name> {
+                vec![$($name::$variant),*].into_iter().collect()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $($name::$variant => write!(f, $label)),*
+                }
+            }
+        }
+
+        impl str::FromStr for $name {
+            type Err = RecurrenceError;
+            fn from_str(s: &str) -> Result<$name, RecurrenceError> {
+                $(
+                    if s.eq_ignore_ascii_case($label) {
+                        return Ok($name::$variant);
+                    }
+                )*
+                Err(RecurrenceError::new(format!("{} is not a valid option", s)))
+            }
+        }
+    };
+}
+
+select_enum!(Frequency {
+    Daily => "Daily",
+    Weekly => "Weekly",
+    Monthly => "Monthly",
+});
+
+select_enum!(MonthlyMode {
+    DayOfMonth => "Day of month",
+    Weekday => "Weekday",
+});
+
+select_enum!(EndKind {
+    Never => "Never",
+    Until => "Until",
+    Count => "Count",
+});
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecurrencePattern {
+    Daily,
+    Weekly(Vec<chrono::Weekday>),
+    MonthlyOnDay(u32),
+    /// eg the 2nd Tuesday with `nth` of 2, or the last Friday with `nth` of -1
+    MonthlyOnWeekday {
+        nth: i32,
+        weekdays: Vec<chrono::Weekday>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceEnd {
+    Never,
+    Until(chrono::NaiveDate),
+    Count(u32),
+}
+
+/// A repeating schedule of dates, covering the daily, weekly and monthly subset of RFC 5545
+/// recurrence rules. `interval` is in units of the pattern's frequency, and weeks start on
+/// Monday.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub start: chrono::NaiveDate,
+    pub interval: u32,
+    pub pattern: RecurrencePattern,
+    pub end: RecurrenceEnd,
+}
+
+const WEEKDAY_CODES: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+fn weekday_code(day: chrono::Weekday) -> &'static str {
+    WEEKDAY_CODES[day.num_days_from_monday() as usize]
+}
+
+fn parse_weekday_code(code: &str) -> Result<chrono::Weekday, RecurrenceError> {
+    let mut day = chrono::Weekday::Mon;
+    for candidate in WEEKDAY_CODES.iter() {
+        if code.eq_ignore_ascii_case(candidate) {
+            return Ok(day);
+        }
+        day = day.succ();
+    }
+    Err(RecurrenceError::new(format!("{} is not a weekday", code)))
+}
+
+fn days_in_month(date: chrono::NaiveDate) -> u32 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    chrono::NaiveDate::from_ymd(year, month, 1).pred().day()
+}
+
+fn week_start(date: chrono::NaiveDate) -> chrono::NaiveDate {
+    date - chrono::Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+impl RecurrenceRule {
+    /// Whether `date` fits the pattern, ignoring the end of the rule.
+    pub fn matches(&self, date: chrono::NaiveDate) -> bool {
+        if date < self.start {
+            return false;
+        }
+        let interval = i64::from(self.interval.max(1));
+        let months = i64::from(date.year() - self.start.year()) * 12 + i64::from(date.month())
+            - i64::from(self.start.month());
+        match &self.pattern {
+            RecurrencePattern::Daily => (date - self.start).num_days() % interval == 0,
+            RecurrencePattern::Weekly(weekdays) => {
+                let weeks = (week_start(date) - week_start(self.start)).num_days() / 7;
+                weeks % interval == 0 && weekdays.contains(&date.weekday())
+            }
+            RecurrencePattern::MonthlyOnDay(day) => months % interval == 0 && date.day() == *day,
+            RecurrencePattern::MonthlyOnWeekday { nth, weekdays } => {
+                let nth_in_month = if *nth > 0 {
+                    ((date.day() - 1) / 7 + 1) as i32
+                } else {
+                    -(((days_in_month(date) - date.day()) / 7 + 1) as i32)
+                };
+                months % interval == 0 && nth_in_month == *nth && weekdays.contains(&date.weekday())
+            }
+        }
+    }
+    /// Up to `n` occurrences on or after `from`, respecting the end of the rule.
+    pub fn occurrences(&self, from: chrono::NaiveDate, n: usize) -> Vec<chrono::NaiveDate> {
+        // a pattern such as the 31st of every 12th month starting in February never matches,
+        // so give up once a gap longer than any valid pattern could produce has passed
+        let max_gap = i64::from(self.interval.max(1)) * 366 + 31;
+        let mut found = Vec::new();
+        let mut seen = 0;
+        let mut last_match = self.start;
+        let mut date = self.start;
+        while found.len() < n && (date - last_match).num_days() <= max_gap {
+            match self.end {
+                RecurrenceEnd::Until(until) if date > until => break,
+                RecurrenceEnd::Count(count) if seen >= count => break,
+                _ => {}
+            }
+            if self.matches(date) {
+                seen += 1;
+                last_match = date;
+                if date >= from {
+                    found.push(date);
+                }
+            }
+            date = date.succ();
+        }
+        found
+    }
+    /// The rule as an RFC 5545 `RRULE` value, eg `FREQ=WEEKLY;BYDAY=MO,WE,FR;UNTIL=20250630`.
+    /// The start date is not part of the rule and should be exported as `DTSTART`.
+    pub fn to_rrule(&self) -> String {
+        let freq = match self.pattern {
+            RecurrencePattern::Daily => "DAILY",
+            RecurrencePattern::Weekly(_) => "WEEKLY",
+            RecurrencePattern::MonthlyOnDay(_) | RecurrencePattern::MonthlyOnWeekday { .. } => {
+                "MONTHLY"
+            }
+        };
+        let mut parts = vec![format!("FREQ={}", freq)];
+        if self.interval != 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        match &self.pattern {
+            RecurrencePattern::Daily => {}
+            RecurrencePattern::Weekly(weekdays) => {
+                let days = weekdays
+                    .iter()
+                    .map(|d| weekday_code(*d))
+                    .collect::<Vec<_>>();
+                parts.push(format!("BYDAY={}", days.join(",")));
+            }
+            RecurrencePattern::MonthlyOnDay(day) => parts.push(format!("BYMONTHDAY={}", day)),
+            RecurrencePattern::MonthlyOnWeekday { nth, weekdays } => {
+                let days = weekdays
+                    .iter()
+                    .map(|d| format!("{}{}", nth, weekday_code(*d)))
+                    .collect::<Vec<_>>();
+                parts.push(format!("BYDAY={}", days.join(",")));
+            }
+        }
+        match self.end {
+            RecurrenceEnd::Never => {}
+            RecurrenceEnd::Until(until) => parts.push(format!("UNTIL={}", until.format("%Y%m%d"))),
+            RecurrenceEnd::Count(count) => parts.push(format!("COUNT={}", count)),
+        }
+        parts.join(";")
+    }
+    /// Parses an RFC 5545 `RRULE` value (with or without the `RRULE:` prefix), starting from
+    /// `start`. Rule parts outside the daily, weekly and monthly subset are rejected rather than
+    /// ignored, so that a rule is never silently changed by a round trip.
+    pub fn from_rrule(
+        rrule: &str,
+        start: chrono::NaiveDate,
+    ) -> Result<RecurrenceRule, RecurrenceError> {
+        let body = rrule.trim();
+        let body = match body.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &body[6..],
+            _ => body,
+        };
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = None;
+        let mut end = RecurrenceEnd::Never;
+        for part in body.split(';').filter(|part| !part.is_empty()) {
+            let mut key_value = part.splitn(2, '=');
+            let key = key_value.next().unwrap_or_default().to_ascii_uppercase();
+            let value = key_value
+                .next()
+                .ok_or_else(|| RecurrenceError::new(format!("{} is missing a value", part)))?;
+            let number = |value: &str| {
+                value
+                    .parse::<u32>()
+                    .map_err(|_| RecurrenceError::new(format!("{} should be a number", part)))
+            };
+            match key.as_str() {
+                "FREQ" => freq = Some(value.to_ascii_uppercase()),
+                "INTERVAL" => interval = number(value)?,
+                "COUNT" => match number(value)? {
+                    0 => return Err(RecurrenceError::new("COUNT must be at least 1".to_string())),
+                    count => end = RecurrenceEnd::Count(count),
+                },
+                "UNTIL" => {
+                    // date-times are truncated to their date
+                    let date = value.get(..8).unwrap_or(value);
+                    end = RecurrenceEnd::Until(
+                        chrono::NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| {
+                            RecurrenceError::new(format!("{} should be a date like 20250630", part))
+                        })?,
+                    );
+                }
+                // negative days (counting from the end of the month) aren't supported
+                "BYMONTHDAY" => {
+                    let day = number(value)?;
+                    day_of_month(&day).map_err(RecurrenceError::new)?;
+                    by_month_day = Some(day);
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        // the weekday code is the last two characters
+                        let split = day
+                            .char_indices()
+                            .rev()
+                            .nth(1)
+                            .map(|(idx, _)| idx)
+                            .unwrap_or(0);
+                        let nth = match &day[..split] {
+                            "" => None,
+                            nth => {
+                                let nth = nth.parse::<i32>().map_err(|_| {
+                                    RecurrenceError::new(format!(
+                                        "{} is not a valid BYDAY entry",
+                                        day
+                                    ))
+                                })?;
+                                nth_weekday(&nth).map_err(RecurrenceError::new)?;
+                                Some(nth)
+                            }
+                        };
+                        by_day.push((nth, parse_weekday_code(&day[split..])?));
+                    }
+                }
+                "WKST" if value.eq_ignore_ascii_case("MO") => {}
+                _ => return Err(RecurrenceError::new(format!("{} is not supported", part))),
+            }
+        }
+        if interval == 0 {
+            return Err(RecurrenceError::new(
+                "INTERVAL must be at least 1".to_string(),
+            ));
+        }
+        let unsupported = || RecurrenceError::new(format!("{} is not supported", body));
+        let pattern = match freq.as_deref() {
+            Some("DAILY") if by_day.is_empty() && by_month_day.is_none() => {
+                RecurrencePattern::Daily
+            }
+            Some("WEEKLY") if by_month_day.is_none() => {
+                if by_day.iter().any(|(nth, _)| nth.is_some()) {
+                    return Err(unsupported());
+                }
+                let mut weekdays = by_day.iter().map(|(_, d)| *d).collect::<Vec<_>>();
+                if weekdays.is_empty() {
+                    weekdays.push(start.weekday());
+                }
+                RecurrencePattern::Weekly(weekdays)
+            }
+            Some("MONTHLY") => match (by_month_day, by_day.first()) {
+                (Some(day), None) => RecurrencePattern::MonthlyOnDay(day),
+                (None, None) => RecurrencePattern::MonthlyOnDay(start.day()),
+                (None, Some((Some(nth), _))) => {
+                    if by_day.iter().any(|(other, _)| other != &Some(*nth)) {
+                        return Err(unsupported());
+                    }
+                    RecurrencePattern::MonthlyOnWeekday {
+                        nth: *nth,
+                        weekdays: by_day.iter().map(|(_, d)| *d).collect(),
+                    }
+                }
+                _ => return Err(unsupported()),
+            },
+            Some(_) => return Err(unsupported()),
+            None => return Err(RecurrenceError::new("FREQ is required".to_string())),
+        };
+        Ok(RecurrenceRule {
+            start,
+            interval,
+            pattern,
+            end,
+        })
+    }
+}
+
+fn at_least_one(input: &u32) -> crate::ValidationResult {
+    if *input >= 1 {
+        Ok(())
+    } else {
+        Err("Input must be at least 1".to_string())
+    }
+}
+
+fn day_of_month(input: &u32) -> crate::ValidationResult {
+    if (1..=31).contains(input) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Day of month should be between 1 and 31 but was {}",
+            input
+        ))
+    }
+}
+
+fn nth_weekday(input: &i32) -> crate::ValidationResult {
+    if (1..=5).contains(input) || (-5..=-1).contains(input) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Week of month should be between 1 and 5, or -1 (last) to -5, but was {}",
+            input
+        ))
+    }
+}
+
+/// A recurrence rule built from the existing inputs. Only the sub-inputs relevant to the
+/// selected frequency, monthly mode and end are parsed, but the others keep their values so
+/// switching back and forth doesn't lose what the user entered.
+pub struct Recurrence {
+    start: inputs::NaiveDate,
+    frequency: inputs::Select<RecurrenceError, Frequency>,
+    interval: inputs::Integer<u32>,
+    weekdays: inputs::Weekdays,
+    monthly_mode: inputs::Select<RecurrenceError, MonthlyMode>,
+    month_day: inputs::Integer<u32>,
+    nth: inputs::Integer<i32>,
+    end: inputs::Select<RecurrenceError, EndKind>,
+    until: inputs::NaiveDate,
+    count: inputs::Integer<u32>,
+}
+
+pub enum RecurrenceMsg {
+    Start(String),
+    Frequency(String),
+    Interval(String),
+    Weekdays(inputs::WeekdaysMsg),
+    MonthlyMode(String),
+    MonthDay(String),
+    Nth(String),
+    End(String),
+    Until(String),
+    Count(String),
+}
+
+impl Recurrence {
+    pub fn new(data: RecurrenceRule, date_format: &'static str) -> Recurrence {
+        let start = data.start;
+        let mut recurrence = Recurrence {
+            start: inputs::NaiveDate::new(start, date_format, crate::Validations::new()),
+            frequency: inputs::Select::new(Frequency::Daily, Frequency::all()),
+            interval: inputs::Integer::new(&1, crate::Validations::from_vec(vec![at_least_one])),
+            weekdays: inputs::Weekdays::new(&[start.weekday()], crate::Validations::new()),
+            monthly_mode: inputs::Select::new(MonthlyMode::DayOfMonth, MonthlyMode::all()),
+            month_day: inputs::Integer::new(
+                &start.day(),
+                crate::Validations::from_vec(vec![day_of_month]),
+            ),
+            nth: inputs::Integer::new(
+                &(((start.day() - 1) / 7 + 1) as i32),
+                crate::Validations::from_vec(vec![nth_weekday]),
+            ),
+            end: inputs::Select::new(EndKind::Never, EndKind::all()),
+            until: inputs::NaiveDate::new(start, date_format, crate::Validations::new()),
+            count: inputs::Integer::new(&1, crate::Validations::from_vec(vec![at_least_one])),
+        };
+        crate::SetInput::set(&mut recurrence, data);
+        recurrence
+    }
+    pub fn get_start(&self) -> &inputs::NaiveDate {
+        &self.start
+    }
+    pub fn get_frequency(&self) -> &inputs::Select<RecurrenceError, Frequency> {
+        &self.frequency
+    }
+    pub fn get_interval(&self) -> &inputs::Integer<u32> {
+        &self.interval
+    }
+    pub fn get_weekdays(&self) -> &inputs::Weekdays {
+        &self.weekdays
+    }
+    pub fn get_monthly_mode(&self) -> &inputs::Select<RecurrenceError, MonthlyMode> {
+        &self.monthly_mode
+    }
+    pub fn get_month_day(&self) -> &inputs::Integer<u32> {
+        &self.month_day
+    }
+    pub fn get_nth(&self) -> &inputs::Integer<i32> {
+        &self.nth
+    }
+    pub fn get_end(&self) -> &inputs::Select<RecurrenceError, EndKind> {
+        &self.end
+    }
+    pub fn get_until(&self) -> &inputs::NaiveDate {
+        &self.until
+    }
+    pub fn get_count(&self) -> &inputs::Integer<u32> {
+        &self.count
+    }
+}
+
+impl crate::SetInput for Recurrence {
+    fn set(&mut self, data: RecurrenceRule) {
+        self.start.set(data.start);
+        self.interval.set(data.interval);
+        match data.pattern {
+            RecurrencePattern::Daily => self.frequency.set(Frequency::Daily),
+            RecurrencePattern::Weekly(weekdays) => {
+                self.frequency.set(Frequency::Weekly);
+                self.weekdays.set(weekdays);
+            }
+            RecurrencePattern::MonthlyOnDay(day) => {
+                self.frequency.set(Frequency::Monthly);
+                self.monthly_mode.set(MonthlyMode::DayOfMonth);
+                self.month_day.set(day);
+            }
+            RecurrencePattern::MonthlyOnWeekday { nth, weekdays } => {
+                self.frequency.set(Frequency::Monthly);
+                self.monthly_mode.set(MonthlyMode::Weekday);
+                self.nth.set(nth);
+                self.weekdays.set(weekdays);
+            }
+        }
+        match data.end {
+            RecurrenceEnd::Never => self.end.set(EndKind::Never),
+            RecurrenceEnd::Until(until) => {
+                self.end.set(EndKind::Until);
+                self.until.set(until);
+            }
+            RecurrenceEnd::Count(count) => {
+                self.end.set(EndKind::Count);
+                self.count.set(count);
+            }
+        }
+    }
+}
+
+impl crate::UserInput for Recurrence {
+    type Output = RecurrenceRule;
+    type Input = RecurrenceMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            RecurrenceMsg::Start(input) => self.start.update(input),
+            RecurrenceMsg::Frequency(input) => self.frequency.update(input),
+            RecurrenceMsg::Interval(input) => self.interval.update(input),
+            RecurrenceMsg::Weekdays(input) => self.weekdays.update(input),
+            RecurrenceMsg::MonthlyMode(input) => self.monthly_mode.update(input),
+            RecurrenceMsg::MonthDay(input) => self.month_day.update(input),
+            RecurrenceMsg::Nth(input) => self.nth.update(input),
+            RecurrenceMsg::End(input) => self.end.update(input),
+            RecurrenceMsg::Until(input) => self.until.update(input),
+            RecurrenceMsg::Count(input) => self.count.update(input),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let start = self.start.parse()?;
+        let interval = self.interval.parse()?;
+        let pattern = match self.frequency.parse()? {
+            Frequency::Daily => RecurrencePattern::Daily,
+            Frequency::Weekly => {
+                let mut weekdays = self.weekdays.parse()?;
+                if weekdays.is_empty() {
+                    weekdays.push(start.weekday());
+                }
+                RecurrencePattern::Weekly(weekdays)
+            }
+            Frequency::Monthly => match self.monthly_mode.parse()? {
+                MonthlyMode::DayOfMonth => RecurrencePattern::MonthlyOnDay(self.month_day.parse()?),
+                MonthlyMode::Weekday => {
+                    let weekdays = self.weekdays.parse()?;
+                    if weekdays.is_empty() {
+                        return Err(crate::Error::Validation(
+                            vec!["Select at least one weekday".to_string()].into(),
+                        ));
+                    }
+                    RecurrencePattern::MonthlyOnWeekday {
+                        nth: self.nth.parse()?,
+                        weekdays,
+                    }
+                }
+            },
+        };
+        let end = match self.end.parse()? {
+            EndKind::Never => RecurrenceEnd::Never,
+            EndKind::Until => {
+                let until = self.until.parse()?;
+                if until < start {
+                    return Err(crate::Error::Validation(
+                        vec![format!(
+                            "Until {} should not be before start {}",
+                            until, start
+                        )]
+                        .into(),
+                    ));
+                }
+                RecurrenceEnd::Until(until)
+            }
+            EndKind::Count => RecurrenceEnd::Count(self.count.parse()?),
+        };
+        Ok(RecurrenceRule {
+            start,
+            interval,
+            pattern,
+            end,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd(2025, 1, 6)
+    }
+
+    fn round_trip(rule: RecurrenceRule) {
+        let parsed = RecurrenceRule::from_rrule(&rule.to_rrule(), rule.start).unwrap();
+        assert_eq!(parsed, rule);
+    }
+
+    #[test]
+    fn round_trips() {
+        round_trip(RecurrenceRule {
+            start: start(),
+            interval: 1,
+            pattern: RecurrencePattern::Daily,
+            end: RecurrenceEnd::Never,
+        });
+        round_trip(RecurrenceRule {
+            start: start(),
+            interval: 2,
+            pattern: RecurrencePattern::Weekly(vec![chrono::Weekday::Mon, chrono::Weekday::Fri]),
+            end: RecurrenceEnd::Count(10),
+        });
+        round_trip(RecurrenceRule {
+            start: start(),
+            interval: 1,
+            pattern: RecurrencePattern::MonthlyOnDay(15),
+            end: RecurrenceEnd::Until(chrono::NaiveDate::from_ymd(2025, 12, 31)),
+        });
+        round_trip(RecurrenceRule {
+            start: start(),
+            interval: 3,
+            pattern: RecurrencePattern::MonthlyOnWeekday {
+                nth: -1,
+                weekdays: vec![chrono::Weekday::Fri],
+            },
+            end: RecurrenceEnd::Never,
+        });
+    }
+
+    #[test]
+    fn parses_prefix_and_lowercase() {
+        let rule = RecurrenceRule::from_rrule("rrule:freq=weekly;byday=tu", start()).unwrap();
+        assert_eq!(
+            rule.pattern,
+            RecurrencePattern::Weekly(vec![chrono::Weekday::Tue])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        for rrule in &[
+            "",
+            "INTERVAL=2",
+            "FREQ=YEARLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;INTERVAL=x",
+            "FREQ=DAILY;COUNT",
+            "FREQ=DAILY;UNTIL=2025-06-30",
+            "FREQ=DAILY;BYHOUR=9",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=WEEKLY;BYDAY=2MO",
+            "FREQ=MONTHLY;BYDAY=1MO,2TU",
+            "FREQ=MONTHLY;BYDAY=xMO",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=DAILY;COUNT=0",
+            "FREQ=MONTHLY;BYMONTHDAY=0",
+            "FREQ=MONTHLY;BYMONTHDAY=40",
+            "FREQ=MONTHLY;BYMONTHDAY=-1",
+        ] {
+            assert!(
+                RecurrenceRule::from_rrule(rrule, start()).is_err(),
+                "{} should be rejected",
+                rrule
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_ascii_without_panicking() {
+        for rrule in &[
+            "FREQ=WEEKLY;BYDAY=éa",
+            "aéééFREQ=DAILY",
+            "é",
+            "RRULé:FREQ=DAILY",
+            "FREQ=DAILY;UNTIL=2025063é",
+            "FREQ=MONTHLY;BYDAY=éMO",
+        ] {
+            assert!(RecurrenceRule::from_rrule(rrule, start()).is_err());
+        }
+    }
+}
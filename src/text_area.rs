@@ -0,0 +1,279 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// The number of user-perceived characters (grapheme clusters) in `text`, so eg `é` written as
+/// `e` plus a combining accent, or a flag emoji, counts as one.
+pub fn character_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Multi-line text with optional limits on its length in characters, its number of lines and the
+/// length of each line. Characters are counted as grapheme clusters. The limits are checked on
+/// the normalised text, and the output is normalised too: optionally with trailing whitespace
+/// removed from each line, and with line endings converted to one style.
+pub struct TextArea {
+    input: String,
+    // kept up to date with the input and options so the counts don't normalise on every call
+    normalised: String,
+    max_characters: Option<usize>,
+    max_lines: Option<usize>,
+    max_line_length: Option<usize>,
+    trim_trailing_whitespace: bool,
+    line_ending: Option<LineEnding>,
+    validations: crate::Validations<String>,
+}
+
+pub enum TextAreaMsg {
+    Input(String),
+    /// Rewrites the raw input in normalised form, eg on blur.
+    Normalise,
+}
+
+impl TextArea {
+    pub fn get_input(&self) -> &str {
+        &self.input
+    }
+    pub fn new(data: &str, validations: crate::Validations<String>) -> TextArea {
+        TextArea {
+            input: data.to_string(),
+            normalised: data.to_string(),
+            max_characters: None,
+            max_lines: None,
+            max_line_length: None,
+            trim_trailing_whitespace: false,
+            line_ending: None,
+            validations,
+        }
+    }
+    pub fn with_max_characters(self, max_characters: usize) -> TextArea {
+        TextArea {
+            max_characters: Some(max_characters),
+            ..self
+        }
+    }
+    pub fn with_max_lines(self, max_lines: usize) -> TextArea {
+        TextArea {
+            max_lines: Some(max_lines),
+            ..self
+        }
+    }
+    pub fn with_max_line_length(self, max_line_length: usize) -> TextArea {
+        TextArea {
+            max_line_length: Some(max_line_length),
+            ..self
+        }
+    }
+    pub fn with_trailing_whitespace_trimmed(self) -> TextArea {
+        TextArea {
+            trim_trailing_whitespace: true,
+            ..self
+        }
+        .renormalised()
+    }
+    pub fn with_line_ending(self, line_ending: LineEnding) -> TextArea {
+        TextArea {
+            line_ending: Some(line_ending),
+            ..self
+        }
+        .renormalised()
+    }
+    pub fn get_max_characters(&self) -> Option<usize> {
+        self.max_characters
+    }
+    pub fn get_max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+    pub fn get_max_line_length(&self) -> Option<usize> {
+        self.max_line_length
+    }
+    /// The input with the configured normalisation applied.
+    pub fn normalised(&self) -> &str {
+        &self.normalised
+    }
+    fn renormalised(mut self) -> TextArea {
+        self.normalised = self.normalise();
+        self
+    }
+    fn set_input(&mut self, input: String) {
+        self.input = input;
+        self.normalised = self.normalise();
+    }
+    fn normalise(&self) -> String {
+        if !self.trim_trailing_whitespace && self.line_ending.is_none() {
+            return self.input.clone();
+        }
+        // `lines` drops the final line ending, so put it back
+        let ends_with_newline = self.input.ends_with('\n');
+        let line_ending = self
+            .line_ending
+            .map(|line_ending| line_ending.as_str())
+            .unwrap_or_else(|| {
+                if self.input.contains("\r\n") {
+                    "\r\n"
+                } else {
+                    "\n"
+                }
+            });
+        let mut normalised = itertools::Itertools::intersperse(
+            self.input.lines().map(|line| {
+                if self.trim_trailing_whitespace {
+                    line.trim_end()
+                } else {
+                    line
+                }
+            }),
+            line_ending,
+        )
+        .collect::<String>();
+        if ends_with_newline {
+            normalised.push_str(line_ending);
+        }
+        normalised
+    }
+    pub fn character_count(&self) -> usize {
+        character_count(&self.normalised)
+    }
+    pub fn line_count(&self) -> usize {
+        self.normalised.lines().count()
+    }
+    /// How many more characters can be entered, or negative if the input is over the limit.
+    pub fn characters_remaining(&self) -> Option<isize> {
+        self.max_characters
+            .map(|max| max as isize - self.character_count() as isize)
+    }
+    /// How many more lines can be entered, or negative if the input is over the limit.
+    pub fn lines_remaining(&self) -> Option<isize> {
+        self.max_lines
+            .map(|max| max as isize - self.line_count() as isize)
+    }
+    fn check_limits(&self, text: &str) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Some(max) = self.max_characters {
+            let count = character_count(text);
+            if count > max {
+                errors.push(format!(
+                    "Text should be at most {} characters but was {}",
+                    max, count
+                ));
+            }
+        }
+        if let Some(max) = self.max_lines {
+            let count = text.lines().count();
+            if count > max {
+                errors.push(format!(
+                    "Text should be at most {} lines but was {}",
+                    max, count
+                ));
+            }
+        }
+        if let Some(max) = self.max_line_length {
+            for (idx, line) in text.lines().enumerate() {
+                let count = character_count(line);
+                if count > max {
+                    errors.push(format!(
+                        "Line {} should be at most {} characters but was {}",
+                        idx + 1,
+                        max,
+                        count
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
+impl crate::SetInput for TextArea {
+    fn set(&mut self, data: String) {
+        self.set_input(data);
+    }
+}
+
+impl crate::UserInput for TextArea {
+    type Output = String;
+    type Input = TextAreaMsg;
+    fn update(&mut self, input: Self::Input) {
+        match input {
+            TextAreaMsg::Input(input) => self.set_input(input),
+            TextAreaMsg::Normalise => self.input = self.normalised.clone(),
+        }
+    }
+    fn parse(&self) -> crate::Result<Self::Output> {
+        let parsed = self.normalised.clone();
+        let errors = self.check_limits(&parsed);
+        if !errors.is_empty() {
+            return Err(crate::Error::Validation(errors.into()));
+        }
+        self.validations.validate(&parsed)?;
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UserInput;
+
+    #[test]
+    fn counts_graphemes() {
+        // `e` with a combining accent, a flag and a family emoji joined with zero width joiners
+        assert_eq!(character_count("e\u{301}🇬🇧👨‍👩‍👧"), 3);
+        let text_area = TextArea::new("e\u{301}\r\nb", crate::Validations::new());
+        assert_eq!(text_area.character_count(), 3);
+        assert_eq!(text_area.line_count(), 2);
+    }
+
+    #[test]
+    fn normalises() {
+        let mut text_area = TextArea::new("a  \r\nb\t\r\n", crate::Validations::new())
+            .with_trailing_whitespace_trimmed()
+            .with_line_ending(LineEnding::Lf);
+        assert_eq!(text_area.normalised(), "a\nb\n");
+        assert_eq!(text_area.character_count(), 4);
+        text_area.update(TextAreaMsg::Input("c \nd".to_string()));
+        assert_eq!(text_area.normalised(), "c\nd");
+        text_area.update(TextAreaMsg::Normalise);
+        assert_eq!(text_area.get_input(), "c\nd");
+        // the input's own line ending is kept when none is configured
+        let text_area =
+            TextArea::new("a \r\nb ", crate::Validations::new()).with_trailing_whitespace_trimmed();
+        assert_eq!(text_area.parse().unwrap(), "a\r\nb");
+    }
+
+    #[test]
+    fn checks_limits() {
+        let text_area = TextArea::new("abcd\nefghij\nk  ", crate::Validations::new())
+            .with_max_characters(10)
+            .with_max_lines(2)
+            .with_max_line_length(5)
+            .with_trailing_whitespace_trimmed();
+        assert_eq!(text_area.characters_remaining(), Some(-3));
+        assert_eq!(text_area.lines_remaining(), Some(-1));
+        match text_area.parse() {
+            Err(crate::Error::Validation(errors)) => assert_eq!(
+                errors.to_string(),
+                "[Text should be at most 10 characters but was 13, \
+                 Text should be at most 2 lines but was 3, \
+                 Line 2 should be at most 5 characters but was 6]"
+            ),
+            other => panic!("expected validation errors but got {:?}", other.err()),
+        }
+        let text_area = TextArea::new("ab", crate::Validations::new()).with_max_characters(2);
+        assert_eq!(text_area.characters_remaining(), Some(0));
+        assert_eq!(text_area.parse().unwrap(), "ab");
+    }
+}